@@ -0,0 +1,2 @@
+! apidoc check --offline --format json api.json
+! apidoc check --offline --format json --type swagger_json api.json
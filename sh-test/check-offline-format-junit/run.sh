@@ -0,0 +1 @@
+! apidoc check --offline --format junit api.json
@@ -0,0 +1 @@
+! apidoc check --offline --format sarif api.json
@@ -0,0 +1 @@
+! apidoc check --offline api.json
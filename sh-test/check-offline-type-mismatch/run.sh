@@ -0,0 +1 @@
+! apidoc check --offline --type swagger_json api.json
@@ -1,8 +1,71 @@
 pub mod client {
     extern crate hyper;
+    extern crate uuid;
     use rustc_serialize::json;
+    use std::collections::BTreeMap;
     use super::models;
 
+    pub struct Applications {
+        base_url: String,
+        token: String
+    }
+
+    impl Applications {
+        pub fn new(base_url: String, token: String) -> Applications {
+            Applications {
+                base_url: base_url,
+                token: token
+            }
+        }
+
+        pub fn get_by_organization_key(
+            &self,
+            organization_key: &str,
+            key: Option<&str>
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push('/');
+            url.push_str(organization_key);
+            let url = try!(with_query(&url[..], &[("key", key)]));
+            let req = client.get(url)
+                .headers(authorization(&self.token));
+            req.send()
+        }
+
+        pub fn post_by_organization_key(
+            &self,
+            organization_key: &str,
+            application_form: models::ApplicationForm
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push('/');
+            url.push_str(organization_key);
+            let json = json::encode(&application_form).unwrap();
+            let req = client.post(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
+                .header(hyper::header::ContentType(application_json()));
+            req.send()
+        }
+
+        pub fn delete_by_organization_key_and_application_key(
+            &self,
+            organization_key: &str,
+            application_key: &str
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push('/');
+            url.push_str(organization_key);
+            url.push('/');
+            url.push_str(application_key);
+            let req = client.delete(&url[..])
+                .headers(authorization(&self.token));
+            req.send()
+        }
+    }
+
     pub struct Code {
         base_url: String,
         token: String
@@ -33,592 +96,1925 @@ pub mod client {
             url.push_str(version);
             url.push('/');
             url.push_str(generator_key);
-            let scheme = hyper::header::Basic {
-                username: self.token.clone(),
-                password: None
-            };
             let req = client.get(&url[..])
-                .header(hyper::header::Authorization(scheme));
+                .headers(authorization(&self.token));
             req.send()
         }
     }
 
-    pub struct Validations {
-        base_url: String
+    pub struct Domains {
+        base_url: String,
+        token: String
     }
 
-    impl Validations {
-        pub fn new(base_url: String) -> Validations {
-            Validations {
-                base_url: base_url
+    impl Domains {
+        pub fn new(base_url: String, token: String) -> Domains {
+            Domains {
+                base_url: base_url,
+                token: token
             }
         }
 
-        pub fn post(&self, value: &str) -> hyper::error::Result<hyper::client::Response> {
+        pub fn post_by_organization_key(
+            &self,
+            organization_key: &str,
+            domain: models::Domain
+        ) -> hyper::error::Result<hyper::client::Response> {
             let mut client = hyper::client::Client::new();
             let mut url = self.base_url.clone();
-            url.push_str("/validations");
-            let req = client.post(&url[..]).body(value);
+            url.push_str("/domains/");
+            url.push_str(organization_key);
+            let json = json::encode(&domain).unwrap();
+            let req = client.post(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
+                .header(hyper::header::ContentType(application_json()));
+            req.send()
+        }
+
+        pub fn delete_by_organization_key_and_name(
+            &self,
+            organization_key: &str,
+            name: &str
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/domains/");
+            url.push_str(organization_key);
+            url.push('/');
+            url.push_str(name);
+            let req = client.delete(&url[..])
+                .headers(authorization(&self.token));
             req.send()
         }
     }
 
-    pub struct Versions {
+    pub struct EmailVerificationConfirmationForms {
         base_url: String,
         token: String
     }
 
-    impl Versions {
-        pub fn new(base_url: String, token: String) -> Versions {
-            Versions {
+    impl EmailVerificationConfirmationForms {
+        pub fn new(base_url: String, token: String) -> EmailVerificationConfirmationForms {
+            EmailVerificationConfirmationForms {
                 base_url: base_url,
                 token: token
             }
         }
 
-        pub fn put_by_organization_key_and_application_key_and_version(
+        pub fn post(
             &self,
-            organization_key: &str,
-            application_key: &str,
-            version: &str,
-            version_form: models::VersionForm
+            email_verification_confirmation_form: models::EmailVerificationConfirmationForm
         ) -> hyper::error::Result<hyper::client::Response> {
             let mut client = hyper::client::Client::new();
             let mut url = self.base_url.clone();
-            url.push('/');
-            url.push_str(organization_key);
-            url.push('/');
-            url.push_str(application_key);
-            url.push('/');
-            url.push_str(version);
-            let json = json::encode(&version_form).unwrap();
-            let scheme = hyper::header::Basic {
-                username: self.token.clone(),
-                password: None
-            };
-            let req = client.put(&url[..]).body(&json[..])
-                .header(hyper::header::Authorization(scheme))
+            url.push_str("/email_verification_confirmations");
+            let json = json::encode(&email_verification_confirmation_form).unwrap();
+            let req = client.post(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
                 .header(hyper::header::ContentType(application_json()));
             req.send()
         }
     }
 
-    fn application_json() -> hyper::mime::Mime {
-        "application/json".parse().unwrap()
+    pub struct Generators {
+        base_url: String,
+        token: String
     }
-}
 
-pub mod models {
-    extern crate chrono;
-    extern crate uuid;
-
-    use rustc_serialize::Decodable;
-    use rustc_serialize::Decoder;
-    use rustc_serialize::Encodable;
-    use rustc_serialize::Encoder;
-    use std;
-    use std::error::Error as StdError;
+    impl Generators {
+        pub fn new(base_url: String, token: String) -> Generators {
+            Generators {
+                base_url: base_url,
+                token: token
+            }
+        }
 
-    /**
-     * An application has a name and multiple versions of its API.
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct Application {
-        pub guid: uuid::Uuid,
-        pub organization: Reference,
-        pub name: String,
-        pub key: String,
-        pub visibility: Visibility,
-        pub description: Option<String>
-    }
+        pub fn get(&self) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/generators");
+            let req = client.get(&url[..])
+                .headers(authorization(&self.token));
+            req.send()
+        }
 
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct ApplicationForm {
-        pub name: String,
-        pub key: Option<String>,
-        pub description: Option<String>,
-        pub visibility: Visibility
-    }
+        pub fn get_by_key(&self, key: &str) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/generators/");
+            url.push_str(key);
+            let req = client.get(&url[..])
+                .headers(authorization(&self.token));
+            req.send()
+        }
 
-    pub struct Audit {
-        pub created_at: chrono::DateTime<chrono::FixedOffset>,
-        pub created_by: ReferenceGuid,
-        pub updated_at: chrono::DateTime<chrono::FixedOffset>,
-        pub updated_by: ReferenceGuid
-    }
+        pub fn post(
+            &self,
+            generator_create_form: models::GeneratorCreateForm
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/generators");
+            let json = json::encode(&generator_create_form).unwrap();
+            let req = client.post(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
+                .header(hyper::header::ContentType(application_json()));
+            req.send()
+        }
 
-    impl ::rustc_serialize::Encodable for Audit {
-        fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
-            e.emit_struct("audit", 4, |e| {
-                try!(e.emit_struct_field("created_at", 0, |e| e.emit_str(&self.created_at.to_rfc3339())));
-                try!(e.emit_struct_field("created_by", 1, |e| self.created_by.encode(e)));
-                try!(e.emit_struct_field("updated_at", 2, |e| e.emit_str(&self.updated_at.to_rfc3339())));
-                try!(e.emit_struct_field("updated_by", 3, |e| self.updated_by.encode(e)));
-                Ok(())
-            })
+        pub fn put_by_key(
+            &self,
+            key: &str,
+            generator_update_form: models::GeneratorUpdateForm
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/generators/");
+            url.push_str(key);
+            let json = json::encode(&generator_update_form).unwrap();
+            let req = client.put(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
+                .header(hyper::header::ContentType(application_json()));
+            req.send()
         }
-    }
 
-    impl ::rustc_serialize::Decodable for Audit {
-        fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
-            d.read_struct("audit", 4, |d| {
-                let created_at = try!(d.read_struct_field("created_at", 0, |d| d.read_str().and_then(|s| {
-                    chrono::DateTime::parse_from_rfc3339(&s).map_err(|err| {
-                        d.error(err.description())
-                    })
-                })));
-                let created_by = try!(d.read_struct_field("created_by", 1, |d| ReferenceGuid::decode(d)));
-                let updated_at = try!(d.read_struct_field("updated_at", 0, |d| d.read_str().and_then(|s| {
-                    chrono::DateTime::parse_from_rfc3339(&s).map_err(|err| {
-                        d.error(err.description())
-                    })
-                })));
-                let updated_by = try!(d.read_struct_field("updated_by", 1, |d| ReferenceGuid::decode(d)));
-                Ok(Audit {
-                    created_at: created_at,
-                    created_by: created_by,
-                    updated_at: updated_at,
-                    updated_by: updated_by,
-                })
-            })
+        pub fn put_orgs_by_key_and_organization_key(
+            &self,
+            key: &str,
+            organization_key: &str,
+            generator_org_form: models::GeneratorOrgForm
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/generators/");
+            url.push_str(key);
+            url.push_str("/orgs/");
+            url.push_str(organization_key);
+            let json = json::encode(&generator_org_form).unwrap();
+            let req = client.put(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
+                .header(hyper::header::ContentType(application_json()));
+            req.send()
         }
     }
 
-    /**
-     * Separate resource used only for the few actions that require the full token.
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct CleartextToken {
-        pub token: String
+    pub struct Healthchecks {
+        base_url: String
     }
 
-    /**
-     * Generated source code.
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct Code {
-        pub generator: Generator,
-        pub source: String
-    }
+    impl Healthchecks {
+        pub fn new(base_url: String) -> Healthchecks {
+            Healthchecks {
+                base_url: base_url
+            }
+        }
 
-    /**
-     * Represents a single domain name (e.g. www.apidoc.me). When a new user registers
-     * and confirms their email, we automatically associate that user with a member of
-     * the organization associated with their domain. For example, if you confirm your
-     * account with an email address of foo@gilt.com, we will automatically create a
-     * membership request on your behalf to join the organization with domain gilt.com.
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct Domain {
-        pub name: String
+        pub fn get_internal_and_healthcheck(&self) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/_internal_/healthcheck");
+            let req = client.get(&url[..]);
+            req.send()
+        }
     }
 
-    /**
-     * Data used to confirm an email address. The token is an internal unique
-     * identifier used to lookup the specific email address and user account for which
-     * we sent an email verification email.
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct EmailVerificationConfirmationForm {
-        pub token: String
+    pub struct MembershipRequests {
+        base_url: String,
+        token: String
     }
 
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct Error {
-        pub code: String,
-        pub message: String
-    }
+    impl MembershipRequests {
+        pub fn new(base_url: String, token: String) -> MembershipRequests {
+            MembershipRequests {
+                base_url: base_url,
+                token: token
+            }
+        }
 
-    /**
-     * An apidoc generator
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct Generator {
+        pub fn get(&self, org_key: Option<&str>) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/membership_requests");
+            let url = try!(with_query(&url[..], &[("org_key", org_key)]));
+            let req = client.get(url)
+                .headers(authorization(&self.token));
+            req.send()
+        }
+
+        pub fn post(
+            &self,
+            org_guid: &uuid::Uuid,
+            user_guid: &uuid::Uuid,
+            role: &str
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/membership_requests");
+            let mut payload = BTreeMap::new();
+            payload.insert("org_guid".to_string(), json::Json::String(org_guid.to_string()));
+            payload.insert("user_guid".to_string(), json::Json::String(user_guid.to_string()));
+            payload.insert("role".to_string(), json::Json::String(role.to_string()));
+            let json = json::Json::Object(payload).to_string();
+            let req = client.post(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
+                .header(hyper::header::ContentType(application_json()));
+            req.send()
+        }
+
+        pub fn post_accept_by_guid(&self, guid: &str) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/membership_requests/");
+            url.push_str(guid);
+            url.push_str("/accept");
+            let req = client.post(&url[..])
+                .headers(authorization(&self.token));
+            req.send()
+        }
+
+        pub fn post_decline_by_guid(&self, guid: &str) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/membership_requests/");
+            url.push_str(guid);
+            url.push_str("/decline");
+            let req = client.post(&url[..])
+                .headers(authorization(&self.token));
+            req.send()
+        }
+    }
+
+    pub struct Memberships {
+        base_url: String,
+        token: String
+    }
+
+    impl Memberships {
+        pub fn new(base_url: String, token: String) -> Memberships {
+            Memberships {
+                base_url: base_url,
+                token: token
+            }
+        }
+
+        pub fn get(
+            &self,
+            org_key: Option<&str>,
+            user_guid: Option<&str>
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/memberships");
+            let url = try!(with_query(&url[..], &[
+                ("org_key", org_key),
+                ("user_guid", user_guid)
+            ]));
+            let req = client.get(url)
+                .headers(authorization(&self.token));
+            req.send()
+        }
+    }
+
+    pub struct Organizations {
+        base_url: String,
+        token: String
+    }
+
+    impl Organizations {
+        pub fn new(base_url: String, token: String) -> Organizations {
+            Organizations {
+                base_url: base_url,
+                token: token
+            }
+        }
+
+        pub fn get(&self) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/organizations");
+            let req = client.get(&url[..])
+                .headers(authorization(&self.token));
+            req.send()
+        }
+
+        pub fn get_by_key(&self, key: &str) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/organizations/");
+            url.push_str(key);
+            let req = client.get(&url[..])
+                .headers(authorization(&self.token));
+            req.send()
+        }
+
+        pub fn post(
+            &self,
+            organization_form: models::OrganizationForm
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/organizations");
+            let json = json::encode(&organization_form).unwrap();
+            let req = client.post(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
+                .header(hyper::header::ContentType(application_json()));
+            req.send()
+        }
+
+        pub fn delete_by_key(&self, key: &str) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/organizations/");
+            url.push_str(key);
+            let req = client.delete(&url[..])
+                .headers(authorization(&self.token));
+            req.send()
+        }
+    }
+
+    pub struct PasswordResetRequests {
+        base_url: String,
+        token: String
+    }
+
+    impl PasswordResetRequests {
+        pub fn new(base_url: String, token: String) -> PasswordResetRequests {
+            PasswordResetRequests {
+                base_url: base_url,
+                token: token
+            }
+        }
+
+        pub fn post(
+            &self,
+            password_reset_request: models::PasswordResetRequest
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/password_reset_requests");
+            let json = json::encode(&password_reset_request).unwrap();
+            let req = client.post(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
+                .header(hyper::header::ContentType(application_json()));
+            req.send()
+        }
+    }
+
+    pub struct PasswordResets {
+        base_url: String,
+        token: String
+    }
+
+    impl PasswordResets {
+        pub fn new(base_url: String, token: String) -> PasswordResets {
+            PasswordResets {
+                base_url: base_url,
+                token: token
+            }
+        }
+
+        pub fn post(
+            &self,
+            password_reset: models::PasswordReset
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/password_resets");
+            let json = json::encode(&password_reset).unwrap();
+            let req = client.post(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
+                .header(hyper::header::ContentType(application_json()));
+            req.send()
+        }
+    }
+
+    pub struct Subscriptions {
+        base_url: String,
+        token: String
+    }
+
+    impl Subscriptions {
+        pub fn new(base_url: String, token: String) -> Subscriptions {
+            Subscriptions {
+                base_url: base_url,
+                token: token
+            }
+        }
+
+        pub fn get(&self, user_guid: Option<&str>) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/subscriptions");
+            let url = try!(with_query(&url[..], &[("user_guid", user_guid)]));
+            let req = client.get(url)
+                .headers(authorization(&self.token));
+            req.send()
+        }
+
+        pub fn post(
+            &self,
+            subscription_form: models::SubscriptionForm
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/subscriptions");
+            let json = json::encode(&subscription_form).unwrap();
+            let req = client.post(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
+                .header(hyper::header::ContentType(application_json()));
+            req.send()
+        }
+
+        pub fn delete_by_guid(&self, guid: &str) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/subscriptions/");
+            url.push_str(guid);
+            let req = client.delete(&url[..])
+                .headers(authorization(&self.token));
+            req.send()
+        }
+    }
+
+    pub struct Tokens {
+        base_url: String,
+        token: String
+    }
+
+    impl Tokens {
+        pub fn new(base_url: String, token: String) -> Tokens {
+            Tokens {
+                base_url: base_url,
+                token: token
+            }
+        }
+
+        pub fn get_users_by_user_guid(
+            &self,
+            user_guid: &str
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/tokens/users/");
+            url.push_str(user_guid);
+            let req = client.get(&url[..])
+                .headers(authorization(&self.token));
+            req.send()
+        }
+
+        pub fn get_cleartext_by_guid(&self, guid: &str) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/tokens/");
+            url.push_str(guid);
+            url.push_str("/cleartext");
+            let req = client.get(&url[..])
+                .headers(authorization(&self.token));
+            req.send()
+        }
+
+        pub fn post(&self, token_form: models::TokenForm) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/tokens");
+            let json = json::encode(&token_form).unwrap();
+            let req = client.post(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
+                .header(hyper::header::ContentType(application_json()));
+            req.send()
+        }
+
+        pub fn delete_by_guid(&self, guid: &str) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/tokens/");
+            url.push_str(guid);
+            let req = client.delete(&url[..])
+                .headers(authorization(&self.token));
+            req.send()
+        }
+    }
+
+    pub struct Users {
+        base_url: String,
+        token: String
+    }
+
+    impl Users {
+        pub fn new(base_url: String, token: String) -> Users {
+            Users {
+                base_url: base_url,
+                token: token
+            }
+        }
+
+        pub fn get(
+            &self,
+            email: Option<&str>,
+            token: Option<&str>
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/users");
+            let url = try!(with_query(&url[..], &[
+                ("email", email),
+                ("token", token)
+            ]));
+            let req = client.get(url)
+                .headers(authorization(&self.token));
+            req.send()
+        }
+
+        pub fn post(&self, user_form: models::UserForm) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/users");
+            let json = json::encode(&user_form).unwrap();
+            let req = client.post(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
+                .header(hyper::header::ContentType(application_json()));
+            req.send()
+        }
+
+        pub fn put_by_guid(
+            &self,
+            guid: &str,
+            user_update_form: models::UserUpdateForm
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/users/");
+            url.push_str(guid);
+            let json = json::encode(&user_update_form).unwrap();
+            let req = client.put(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
+                .header(hyper::header::ContentType(application_json()));
+            req.send()
+        }
+
+        pub fn post_authenticate(
+            &self,
+            email: &str,
+            password: &str
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/users/authenticate");
+            let mut payload = BTreeMap::new();
+            payload.insert("email".to_string(), json::Json::String(email.to_string()));
+            payload.insert("password".to_string(), json::Json::String(password.to_string()));
+            let json = json::Json::Object(payload).to_string();
+            let req = client.post(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
+                .header(hyper::header::ContentType(application_json()));
+            req.send()
+        }
+    }
+
+    pub struct Validations {
+        base_url: String
+    }
+
+    impl Validations {
+        pub fn new(base_url: String) -> Validations {
+            Validations {
+                base_url: base_url
+            }
+        }
+
+        pub fn post(&self, value: &str) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/validations");
+            let req = client.post(&url[..]).body(value);
+            req.send()
+        }
+    }
+
+    pub struct Versions {
+        base_url: String,
+        token: String
+    }
+
+    impl Versions {
+        pub fn new(base_url: String, token: String) -> Versions {
+            Versions {
+                base_url: base_url,
+                token: token
+            }
+        }
+
+        pub fn get_by_organization_key_and_application_key(
+            &self,
+            organization_key: &str,
+            application_key: &str,
+            limit: Option<i64>,
+            offset: Option<i64>
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push('/');
+            url.push_str(organization_key);
+            url.push('/');
+            url.push_str(application_key);
+            let limit = limit.map(|limit| limit.to_string());
+            let offset = offset.map(|offset| offset.to_string());
+            let url = try!(with_query(&url[..], &[
+                ("limit", limit.as_ref().map(|s| &s[..])),
+                ("offset", offset.as_ref().map(|s| &s[..]))
+            ]));
+            let req = client.get(url)
+                .headers(authorization(&self.token));
+            req.send()
+        }
+
+        pub fn get_by_organization_key_and_application_key_and_version(
+            &self,
+            organization_key: &str,
+            application_key: &str,
+            version: &str
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push('/');
+            url.push_str(organization_key);
+            url.push('/');
+            url.push_str(application_key);
+            url.push('/');
+            url.push_str(version);
+            let req = client.get(&url[..])
+                .headers(authorization(&self.token));
+            req.send()
+        }
+
+        pub fn delete_by_organization_key_and_application_key_and_version(
+            &self,
+            organization_key: &str,
+            application_key: &str,
+            version: &str
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push('/');
+            url.push_str(organization_key);
+            url.push('/');
+            url.push_str(application_key);
+            url.push('/');
+            url.push_str(version);
+            let req = client.delete(&url[..])
+                .headers(authorization(&self.token));
+            req.send()
+        }
+
+        pub fn put_by_organization_key_and_application_key_and_version(
+            &self,
+            organization_key: &str,
+            application_key: &str,
+            version: &str,
+            version_form: models::VersionForm
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push('/');
+            url.push_str(organization_key);
+            url.push('/');
+            url.push_str(application_key);
+            url.push('/');
+            url.push_str(version);
+            let json = json::encode(&version_form).unwrap();
+            let req = client.put(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
+                .header(hyper::header::ContentType(application_json()));
+            req.send()
+        }
+    }
+
+    pub struct Watches {
+        base_url: String,
+        token: String
+    }
+
+    impl Watches {
+        pub fn new(base_url: String, token: String) -> Watches {
+            Watches {
+                base_url: base_url,
+                token: token
+            }
+        }
+
+        pub fn get(
+            &self,
+            user_guid: Option<&str>,
+            organization_key: Option<&str>,
+            application_key: Option<&str>
+        ) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/watches");
+            let url = try!(with_query(&url[..], &[
+                ("user_guid", user_guid),
+                ("organization_key", organization_key),
+                ("application_key", application_key)
+            ]));
+            let req = client.get(url)
+                .headers(authorization(&self.token));
+            req.send()
+        }
+
+        pub fn post(&self, watch_form: models::WatchForm) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/watches");
+            let json = json::encode(&watch_form).unwrap();
+            let req = client.post(&url[..]).body(&json[..])
+                .headers(authorization(&self.token))
+                .header(hyper::header::ContentType(application_json()));
+            req.send()
+        }
+
+        pub fn delete_by_guid(&self, guid: &str) -> hyper::error::Result<hyper::client::Response> {
+            let mut client = hyper::client::Client::new();
+            let mut url = self.base_url.clone();
+            url.push_str("/watches/");
+            url.push_str(guid);
+            let req = client.delete(&url[..])
+                .headers(authorization(&self.token));
+            req.send()
+        }
+    }
+
+    fn application_json() -> hyper::mime::Mime {
+        "application/json".parse().unwrap()
+    }
+
+    fn with_query(url: &str, params: &[(&str, Option<&str>)]) -> hyper::error::Result<hyper::Url> {
+        let mut url = try!(hyper::Url::parse(url));
+        let pairs: Vec<(&str, &str)> = params.iter()
+            .filter_map(|&(name, value)| value.map(|value| (name, value)))
+            .collect();
+        if !pairs.is_empty() {
+            url.set_query_from_pairs(pairs.into_iter());
+        }
+        Ok(url)
+    }
+
+    // The headers authenticating a request with `token`. Anonymous
+    // clients have no token, and send no `Authorization` header at all.
+    fn authorization(token: &str) -> hyper::header::Headers {
+        let mut headers = hyper::header::Headers::new();
+        if !token.is_empty() {
+            headers.set(hyper::header::Authorization(hyper::header::Basic {
+                username: token.to_string(),
+                password: None
+            }));
+        }
+        headers
+    }
+}
+
+pub mod models {
+    extern crate chrono;
+    extern crate uuid;
+
+    use rustc_serialize::Decodable;
+    use rustc_serialize::Decoder;
+    use rustc_serialize::Encodable;
+    use rustc_serialize::Encoder;
+    use std;
+    use std::error::Error as StdError;
+
+    /**
+     * An application has a name and multiple versions of its API.
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct Application {
+        pub guid: uuid::Uuid,
+        pub organization: Reference,
+        pub name: String,
+        pub key: String,
+        pub visibility: Visibility,
+        pub description: Option<String>
+    }
+
+    #[derive(Clone, RustcEncodable, RustcDecodable)]
+    pub struct ApplicationForm {
+        pub name: String,
+        pub key: Option<String>,
+        pub description: Option<String>,
+        pub visibility: Visibility
+    }
+
+    pub struct Audit {
+        pub created_at: chrono::DateTime<chrono::FixedOffset>,
+        pub created_by: ReferenceGuid,
+        pub updated_at: chrono::DateTime<chrono::FixedOffset>,
+        pub updated_by: ReferenceGuid
+    }
+
+    impl ::rustc_serialize::Encodable for Audit {
+        fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
+            e.emit_struct("audit", 4, |e| {
+                try!(e.emit_struct_field("created_at", 0, |e| e.emit_str(&self.created_at.to_rfc3339())));
+                try!(e.emit_struct_field("created_by", 1, |e| self.created_by.encode(e)));
+                try!(e.emit_struct_field("updated_at", 2, |e| e.emit_str(&self.updated_at.to_rfc3339())));
+                try!(e.emit_struct_field("updated_by", 3, |e| self.updated_by.encode(e)));
+                Ok(())
+            })
+        }
+    }
+
+    impl ::rustc_serialize::Decodable for Audit {
+        fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
+            d.read_struct("audit", 4, |d| {
+                let created_at = try!(d.read_struct_field("created_at", 0, |d| d.read_str().and_then(|s| {
+                    chrono::DateTime::parse_from_rfc3339(&s).map_err(|err| {
+                        d.error(err.description())
+                    })
+                })));
+                let created_by = try!(d.read_struct_field("created_by", 1, |d| ReferenceGuid::decode(d)));
+                let updated_at = try!(d.read_struct_field("updated_at", 0, |d| d.read_str().and_then(|s| {
+                    chrono::DateTime::parse_from_rfc3339(&s).map_err(|err| {
+                        d.error(err.description())
+                    })
+                })));
+                let updated_by = try!(d.read_struct_field("updated_by", 1, |d| ReferenceGuid::decode(d)));
+                Ok(Audit {
+                    created_at: created_at,
+                    created_by: created_by,
+                    updated_at: updated_at,
+                    updated_by: updated_by,
+                })
+            })
+        }
+    }
+
+    /**
+     * Separate resource used only for the few actions that require the full token.
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct CleartextToken {
+        pub token: String
+    }
+
+    /**
+     * Generated source code.
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct Code {
+        pub generator: Generator,
+        pub source: String
+    }
+
+    /**
+     * Represents a single domain name (e.g. www.apidoc.me). When a new user registers
+     * and confirms their email, we automatically associate that user with a member of
+     * the organization associated with their domain. For example, if you confirm your
+     * account with an email address of foo@gilt.com, we will automatically create a
+     * membership request on your behalf to join the organization with domain gilt.com.
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct Domain {
+        pub name: String
+    }
+
+    /**
+     * Data used to confirm an email address. The token is an internal unique
+     * identifier used to lookup the specific email address and user account for which
+     * we sent an email verification email.
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct EmailVerificationConfirmationForm {
+        pub token: String
+    }
+
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct Error {
+        pub code: String,
+        pub message: String
+    }
+
+    /**
+     * An apidoc generator
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct Generator {
+        pub guid: uuid::Uuid,
+        pub key: String,
+        pub uri: String,
+        pub name: String,
+        pub language: Option<String>,
+        pub description: Option<String>,
+        pub visibility: Visibility
+    }
+
+    /**
+     * Form to create a new generator
+     */
+    #[derive(Clone, RustcEncodable, RustcDecodable)]
+    pub struct GeneratorCreateForm {
+        pub key: String,
+        pub uri: String,
+        pub visibility: Visibility
+    }
+
+    /**
+     * Form to enable or disable a generator for an organization
+     */
+    #[derive(Clone, RustcEncodable, RustcDecodable)]
+    pub struct GeneratorOrgForm {
+        pub enabled: bool
+    }
+
+    /**
+     * Form to update a generator
+     */
+    #[derive(Clone, RustcEncodable, RustcDecodable)]
+    pub struct GeneratorUpdateForm {
+        pub visibility: Option<Visibility>,
+        pub enabled: Option<bool>
+    }
+
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct Healthcheck {
+        pub status: String
+    }
+
+    /**
+     * A membership represents a user in a specific role to an organization.
+     * Memberships cannot be created directly. Instead you first create a membership
+     * request, then that request is either accepted or declined.
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct Membership {
+        pub guid: uuid::Uuid,
+        pub user: User,
+        pub organization: Organization,
+        pub role: String
+    }
+
+    /**
+     * A membership request represents a user requesting to join an organization with a
+     * specific role (e.g. as a member or an admin). Membership requests can be
+     * reviewed by any current admin of the organization who can either accept or
+     * decline the request.
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct MembershipRequest {
+        pub guid: uuid::Uuid,
+        pub user: User,
+        pub organization: Organization,
+        pub role: String
+    }
+
+    /**
+     * An organization is used to group a set of applications together.
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct Organization {
+        pub guid: uuid::Uuid,
+        pub key: String,
+        pub name: String,
+        pub namespace: String,
+        pub visibility: Visibility,
+        pub domains: Vec<Domain>
+    }
+
+    #[derive(Clone, RustcEncodable, RustcDecodable)]
+    pub struct OrganizationForm {
+        pub name: String,
+        pub key: Option<String>,
+        pub namespace: String,
+        pub visibility: Option<Visibility>,
+        pub domains: Vec<String>
+    }
+
+    /**
+     * Represents the original input used to create an application version
+     */
+    pub struct Original {
+        pub original_type: OriginalType,
+        pub data: String
+    }
+
+    impl Encodable for Original {
+        fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
+            e.emit_struct("original", 2, |e| {
+                try!(e.emit_struct_field("type", 0, |e| self.original_type.encode(e)));
+                try!(e.emit_struct_field("data", 1, |e| self.data.encode(e)));
+                Ok(())
+            })
+        }
+    }
+
+    impl Decodable for Original {
+        fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
+            d.read_struct("original", 2, |d| {
+                let original_type: OriginalType = try!(
+                    d.read_struct_field("type", 0, OriginalType::decode));
+                let data: String = try!(
+                    d.read_struct_field("data", 1, |d| d.read_str()));
+                Ok(Original {
+                    original_type: original_type,
+                    data: data
+                })
+            })
+        }
+    }
+
+    pub struct OriginalForm {
+        pub original_type: Option<OriginalType>,
+        pub data: String
+    }
+
+    impl Encodable for OriginalForm {
+        fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
+            e.emit_struct("original_form", 2, |e| {
+                try!(e.emit_struct_field("type", 0, |e| self.original_type.encode(e)));
+                try!(e.emit_struct_field("data", 1, |e| self.data.encode(e)));
+                Ok(())
+            })
+        }
+    }
+
+    impl Decodable for OriginalForm {
+        fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
+            d.read_struct("original_form", 2, |d| {
+                let original_type: Option<OriginalType> = try!(
+                    d.read_struct_field("type", 0, Option::<OriginalType>::decode));
+                let data: String = try!(
+                    d.read_struct_field("data", 1, |d| d.read_str()));
+                Ok(OriginalForm {
+                    original_type: original_type,
+                    data: data
+                })
+            })
+        }
+    }
+
+    /**
+     * Allows a user to change their password with authentication from a token.
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct PasswordReset {
+        pub token: String,
+        pub password: String
+    }
+
+    /**
+     * Create a password reset request - e.g. an email containing a one time URL to
+     * change a password
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct PasswordResetRequest {
+        pub email: String
+    }
+
+    /**
+     * On a successful password reset, return some metadata about the user modified.
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct PasswordResetSuccess {
+        pub user_guid: uuid::Uuid
+    }
+
+    /**
+     * Represents a reference to another model.
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct Reference {
+        pub guid: uuid::Uuid,
+        pub key: String
+    }
+
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct ReferenceGuid {
+        pub guid: uuid::Uuid
+    }
+
+    /**
+     * Represents a user that is currently subscribed to a publication
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct Subscription {
+        pub guid: uuid::Uuid,
+        pub organization: Organization,
+        pub user: User,
+        pub publication: Publication
+    }
+
+    #[derive(Clone, RustcEncodable, RustcDecodable)]
+    pub struct SubscriptionForm {
+        pub organization_key: String,
+        pub user_guid: uuid::Uuid,
+        pub publication: Publication
+    }
+
+    /**
+     * A token gives a user access to the API.
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct Token {
         pub guid: uuid::Uuid,
-        pub key: String,
-        pub uri: String,
-        pub name: String,
-        pub language: Option<String>,
+        pub user: User,
+        pub masked_token: String,
         pub description: Option<String>,
-        pub visibility: Visibility
+        pub audit: Audit
+    }
+
+    #[derive(Clone, RustcEncodable, RustcDecodable)]
+    pub struct TokenForm {
+        pub user_guid: uuid::Uuid,
+        pub description: Option<String>
+    }
+
+    /**
+     * A user is a top level person interacting with the api doc server.
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct User {
+        pub guid: uuid::Uuid,
+        pub email: String,
+        pub nickname: String,
+        pub name: Option<String>
+    }
+
+    #[derive(Clone, RustcEncodable, RustcDecodable)]
+    pub struct UserForm {
+        pub email: String,
+        pub password: String,
+        pub nickname: Option<String>,
+        pub name: Option<String>
+    }
+
+    #[derive(Clone, RustcEncodable, RustcDecodable)]
+    pub struct UserUpdateForm {
+        pub email: String,
+        pub nickname: String,
+        pub name: Option<String>
+    }
+
+    /**
+     * Used only to validate json files - used as a resource where http status code
+     * defines success
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct Validation {
+        pub valid: bool,
+        pub errors: Vec<String>
+    }
+
+    /**
+     * Represents a unique version of the application.
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct Version {
+        pub guid: uuid::Uuid,
+        pub organization: Reference,
+        pub application: Reference,
+        pub version: String,
+        pub original: Option<Original>,
+        pub service: Option<super::spec::Service>
+    }
+
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct VersionForm {
+        pub original_form: OriginalForm,
+        pub visibility: Option<Visibility>
+    }
+
+    /**
+     * Users can watch individual applications which enables features like receiving an
+     * email notification when there is a new version of an application.
+     */
+    #[derive(RustcEncodable, RustcDecodable)]
+    pub struct Watch {
+        pub guid: uuid::Uuid,
+        pub user: User,
+        pub organization: Organization,
+        pub application: Application
+    }
+
+    #[derive(Clone, RustcEncodable, RustcDecodable)]
+    pub struct WatchForm {
+        pub user_guid: uuid::Uuid,
+        pub organization_key: String,
+        pub application_key: String
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum OriginalType {
+
+        /**
+         * The original is in the api.json format
+         */
+        ApiJson,
+
+        /**
+         * The original in the swagger.json format
+         */
+        SwaggerJson,
+
+        /**
+         * The original is in Avro Idl format
+         */
+        AvroIdl,
+
+        /**
+         * UNDEFINED captures values that are sent either in error or
+         * that were added by the server after this library was
+         * generated. We want to make it easy and obvious for users of
+         * this library to handle this case gracefully.
+         *
+         * We use all CAPS for the variable name to avoid collisions
+         * with the camel cased values above.
+         */
+        UNDEFINED(String)
+    }
+
+    impl Encodable for OriginalType {
+        fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
+            match self {
+                &OriginalType::ApiJson => e.emit_str("api_json"),
+                &OriginalType::SwaggerJson => e.emit_str("swagger_json"),
+                &OriginalType::AvroIdl => e.emit_str("avro_idl"),
+                &OriginalType::UNDEFINED(ref value) => e.emit_str(&value)
+            }
+        }
+    }
+
+    impl Decodable for OriginalType {
+        fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
+            d.read_str().map(|value| {
+                match &value[..] {
+                    "api_json" => OriginalType::ApiJson,
+                    "swagger_json" => OriginalType::SwaggerJson,
+                    "avro_idl" => OriginalType::AvroIdl,
+                    _ => OriginalType::UNDEFINED(value)
+                }
+            })
+        }
+    }
+
+    impl OriginalType {
+        pub fn valid(&self) -> Result<&Self, &Self> {
+            match self {
+                &OriginalType::UNDEFINED(_) => Err(self),
+                _ => Ok(self)
+            }
+        }
+    }
+
+    impl std::fmt::Display for OriginalType {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            match self {
+                &OriginalType::ApiJson => f.write_str("api_json"),
+                &OriginalType::SwaggerJson => f.write_str("swagger_json"),
+                &OriginalType::AvroIdl => f.write_str("avro_idl"),
+                &OriginalType::UNDEFINED(ref value) => f.write_str(value)
+            }
+        }
+    }
+
+    /**
+     * A publication represents something that a user can subscribe to. An example
+     * would be subscribing to an email alert whenever a new version of an application
+     * is created.
+     */
+    #[derive(Clone, Debug)]
+    pub enum Publication {
+
+        /**
+         * For organizations for which I am an administrator, email me whenever a user
+         * applies to join the org.
+         */
+        MembershipRequestsCreate,
+
+        /**
+         * For organizations for which I am a member, email me whenever a user joins the
+         * org.
+         */
+        MembershipsCreate,
+
+        /**
+         * For organizations for which I am a member, email me whenever an application is
+         * created.
+         */
+        ApplicationsCreate,
+
+        /**
+         * For applications that I watch, email me whenever a version is created.
+         */
+        VersionsCreate,
+
+        /**
+         * UNDEFINED captures values that are sent either in error or
+         * that were added by the server after this library was
+         * generated. We want to make it easy and obvious for users of
+         * this library to handle this case gracefully.
+         *
+         * We use all CAPS for the variable name to avoid collisions
+         * with the camel cased values above.
+         */
+        UNDEFINED(String)
+    }
+
+    impl Publication {
+        pub fn valid(&self) -> Result<&Self, &Self> {
+            match self {
+                &Publication::UNDEFINED(_) => Err(self),
+                _ => Ok(self)
+            }
+        }
+    }
+
+    impl std::fmt::Display for Publication {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            match self {
+                &Publication::MembershipRequestsCreate => f.write_str("membership_requests.create"),
+                &Publication::MembershipsCreate => f.write_str("memberships.create"),
+                &Publication::ApplicationsCreate => f.write_str("applications.create"),
+                &Publication::VersionsCreate => f.write_str("versions.create"),
+                &Publication::UNDEFINED(ref value) => f.write_str(value)
+            }
+        }
+    }
+
+    impl Encodable for Publication {
+        fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
+            match self {
+                &Publication::MembershipRequestsCreate => e.emit_str("membership_requests.create"),
+                &Publication::MembershipsCreate => e.emit_str("memberships.create"),
+                &Publication::ApplicationsCreate => e.emit_str("applications.create"),
+                &Publication::VersionsCreate => e.emit_str("versions.create"),
+                &Publication::UNDEFINED(ref value) => e.emit_str(value)
+            }
+        }
+    }
+
+    impl Decodable for Publication {
+        fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
+            d.read_str().map(|value| {
+                match &value[..] {
+                    "membership_requests.create" => Publication::MembershipRequestsCreate,
+                    "memberships.create" => Publication::MembershipsCreate,
+                    "applications.create" => Publication::ApplicationsCreate,
+                    "versions.create" => Publication::VersionsCreate,
+                    _ => Publication::UNDEFINED(value)
+                }
+            })
+        }
+    }
+
+    /**
+     * Controls who is able to view this version
+     */
+    #[derive(Clone, Debug)]
+    pub enum Visibility {
+
+        /**
+         * Only the creator can view this application
+         */
+        User,
+
+        /**
+         * Any member of the organization can view this application
+         */
+        Organization,
+
+        /**
+         * Anybody, including non logged in users, can view this application
+         */
+        Public,
+
+        /**
+         * UNDEFINED captures values that are sent either in error or
+         * that were added by the server after this library was
+         * generated. We want to make it easy and obvious for users of
+         * this library to handle this case gracefully.
+         *
+         * We use all CAPS for the variable name to avoid collisions
+         * with the camel cased values above.
+         */
+        UNDEFINED(String)
     }
 
-    /**
-     * Form to create a new generator
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct GeneratorCreateForm {
-        pub key: String,
-        pub uri: String,
-        pub visibility: Visibility
+    impl Visibility {
+        pub fn valid(&self) -> Result<&Self, &Self> {
+            match self {
+                &Visibility::UNDEFINED(_) => Err(self),
+                _ => Ok(self)
+            }
+        }
     }
 
-    /**
-     * Form to enable or disable a generator for an organization
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct GeneratorOrgForm {
-        pub enabled: bool
+    impl std::fmt::Display for Visibility {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            match self {
+                &Visibility::User => f.write_str("user"),
+                &Visibility::Organization => f.write_str("organization"),
+                &Visibility::Public => f.write_str("public"),
+                &Visibility::UNDEFINED(ref value) => f.write_str(value)
+            }
+        }
     }
 
-    /**
-     * Form to update a generator
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct GeneratorUpdateForm {
-        pub visibility: Option<Visibility>,
-        pub enabled: Option<bool>
+    impl ::rustc_serialize::Encodable for Visibility {
+        fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
+            match self {
+                &Visibility::User => e.emit_str("user"),
+                &Visibility::Organization => e.emit_str("organization"),
+                &Visibility::Public => e.emit_str("public"),
+                &Visibility::UNDEFINED(ref value) => e.emit_str(value)
+            }
+        }
+    }
+
+    impl ::rustc_serialize::Decodable for Visibility {
+        fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
+            d.read_str().map(|value| {
+                match &value[..] {
+                    "user" => Visibility::User,
+                    "organization" => Visibility::Organization,
+                    "public" => Visibility::Public,
+                    _ => Visibility::UNDEFINED(value)
+                }
+            })
+        }
+    }
+}
+
+/**
+ * The models of com.gilt.apidoc.spec.v0, which describe a service once it
+ * has been resolved by the server, as found in `models::Version::service`.
+ */
+pub mod spec {
+    use rustc_serialize::Decodable;
+    use rustc_serialize::Decoder;
+    use rustc_serialize::Encodable;
+    use rustc_serialize::Encoder;
+    use std;
+
+    // Reads an array that may be left out, as an empty one, so that a
+    // service that omits an empty list can still be decoded.
+    fn read_list<D: Decoder, T: Decodable>(
+        d: &mut D,
+        name: &str,
+        idx: usize
+    ) -> Result<Vec<T>, D::Error> {
+        d.read_struct_field(name, idx, Option::<Vec<T>>::decode).map(|list| list.unwrap_or(Vec::new()))
+    }
+
+    // Reads a `required` flag, which defaults to true when left out.
+    fn read_required<D: Decoder>(d: &mut D, idx: usize) -> Result<bool, D::Error> {
+        d.read_struct_field("required", idx, Option::<bool>::decode).map(|required| required.unwrap_or(true))
     }
 
     #[derive(RustcEncodable, RustcDecodable)]
-    pub struct Healthcheck {
-        pub status: String
+    pub struct Apidoc {
+        pub version: String
     }
 
-    /**
-     * A membership represents a user in a specific role to an organization.
-     * Memberships cannot be created directly. Instead you first create a membership
-     * request, then that request is either accepted or declined.
-     */
     #[derive(RustcEncodable, RustcDecodable)]
-    pub struct Membership {
-        pub guid: uuid::Uuid,
-        pub user: User,
-        pub organization: Organization,
-        pub role: String
+    pub struct Application {
+        pub key: String
+    }
+
+    pub struct Body {
+        pub body_type: String,
+        pub description: Option<String>,
+        pub deprecation: Option<Deprecation>
+    }
+
+    impl Encodable for Body {
+        fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
+            e.emit_struct("body", 3, |e| {
+                try!(e.emit_struct_field("type", 0, |e| self.body_type.encode(e)));
+                try!(e.emit_struct_field("description", 1, |e| self.description.encode(e)));
+                try!(e.emit_struct_field("deprecation", 2, |e| self.deprecation.encode(e)));
+                Ok(())
+            })
+        }
+    }
+
+    impl Decodable for Body {
+        fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
+            d.read_struct("body", 3, |d| {
+                Ok(Body {
+                    body_type: try!(d.read_struct_field("type", 0, |d| d.read_str())),
+                    description: try!(d.read_struct_field("description", 1, Option::<String>::decode)),
+                    deprecation: try!(d.read_struct_field("deprecation", 2, Option::<Deprecation>::decode))
+                })
+            })
+        }
     }
 
     /**
-     * A membership request represents a user requesting to join an organization with a
-     * specific role (e.g. as a member or an admin). Membership requests can be
-     * reviewed by any current admin of the organization who can either accept or
-     * decline the request.
+     * Describes the primary contact for this service
      */
     #[derive(RustcEncodable, RustcDecodable)]
-    pub struct MembershipRequest {
-        pub guid: uuid::Uuid,
-        pub user: User,
-        pub organization: Organization,
-        pub role: String
+    pub struct Contact {
+        pub name: Option<String>,
+        pub url: Option<String>,
+        pub email: Option<String>
     }
 
     /**
-     * An organization is used to group a set of applications together.
+     * Indicates that this particular element is considered deprecated in the API.
+     * See the description for details
      */
     #[derive(RustcEncodable, RustcDecodable)]
-    pub struct Organization {
-        pub guid: uuid::Uuid,
-        pub key: String,
+    pub struct Deprecation {
+        pub description: Option<String>
+    }
+
+    #[derive(RustcEncodable)]
+    pub struct Enum {
         pub name: String,
-        pub namespace: String,
-        pub visibility: Visibility,
-        pub domains: Vec<Domain>
+        pub plural: String,
+        pub description: Option<String>,
+        pub deprecation: Option<Deprecation>,
+        pub values: Vec<EnumValue>
+    }
+
+    impl Decodable for Enum {
+        fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
+            d.read_struct("enum", 5, |d| {
+                Ok(Enum {
+                    name: try!(d.read_struct_field("name", 0, |d| d.read_str())),
+                    plural: try!(d.read_struct_field("plural", 1, |d| d.read_str())),
+                    description: try!(d.read_struct_field("description", 2, Option::<String>::decode)),
+                    deprecation: try!(d.read_struct_field("deprecation", 3, Option::<Deprecation>::decode)),
+                    values: try!(read_list(d, "values", 4))
+                })
+            })
+        }
     }
 
     #[derive(RustcEncodable, RustcDecodable)]
-    pub struct OrganizationForm {
+    pub struct EnumValue {
         pub name: String,
-        pub key: Option<String>,
-        pub namespace: String,
-        pub visibility: Option<Visibility>,
-        pub domains: Vec<String>
+        pub description: Option<String>,
+        pub deprecation: Option<Deprecation>
     }
 
-    /**
-     * Represents the original input used to create an application version
-     */
-    pub struct Original {
-        pub original_type: OriginalType,
-        pub data: String
+    pub struct Field {
+        pub name: String,
+        pub field_type: String,
+        pub description: Option<String>,
+        pub deprecation: Option<Deprecation>,
+        pub default: Option<String>,
+        pub required: bool,
+        pub minimum: Option<i64>,
+        pub maximum: Option<i64>,
+        pub example: Option<String>
     }
 
-    impl Encodable for Original {
+    impl Encodable for Field {
         fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
-            e.emit_struct("original", 2, |e| {
-                try!(e.emit_struct_field("type", 0, |e| self.original_type.encode(e)));
-                try!(e.emit_struct_field("data", 1, |e| self.data.encode(e)));
+            e.emit_struct("field", 9, |e| {
+                try!(e.emit_struct_field("name", 0, |e| self.name.encode(e)));
+                try!(e.emit_struct_field("type", 1, |e| self.field_type.encode(e)));
+                try!(e.emit_struct_field("description", 2, |e| self.description.encode(e)));
+                try!(e.emit_struct_field("deprecation", 3, |e| self.deprecation.encode(e)));
+                try!(e.emit_struct_field("default", 4, |e| self.default.encode(e)));
+                try!(e.emit_struct_field("required", 5, |e| self.required.encode(e)));
+                try!(e.emit_struct_field("minimum", 6, |e| self.minimum.encode(e)));
+                try!(e.emit_struct_field("maximum", 7, |e| self.maximum.encode(e)));
+                try!(e.emit_struct_field("example", 8, |e| self.example.encode(e)));
                 Ok(())
             })
         }
     }
 
-    impl Decodable for Original {
+    impl Decodable for Field {
         fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
-            d.read_struct("original", 2, |d| {
-                let original_type: OriginalType = try!(
-                    d.read_struct_field("type", 0, OriginalType::decode));
-                let data: String = try!(
-                    d.read_struct_field("data", 1, |d| d.read_str()));
-                Ok(Original {
-                    original_type: original_type,
-                    data: data
+            d.read_struct("field", 9, |d| {
+                Ok(Field {
+                    name: try!(d.read_struct_field("name", 0, |d| d.read_str())),
+                    field_type: try!(d.read_struct_field("type", 1, |d| d.read_str())),
+                    description: try!(d.read_struct_field("description", 2, Option::<String>::decode)),
+                    deprecation: try!(d.read_struct_field("deprecation", 3, Option::<Deprecation>::decode)),
+                    default: try!(d.read_struct_field("default", 4, Option::<String>::decode)),
+                    required: try!(read_required(d, 5)),
+                    minimum: try!(d.read_struct_field("minimum", 6, Option::<i64>::decode)),
+                    maximum: try!(d.read_struct_field("maximum", 7, Option::<i64>::decode)),
+                    example: try!(d.read_struct_field("example", 8, Option::<String>::decode))
                 })
             })
         }
     }
 
-    pub struct OriginalForm {
-        pub original_type: Option<OriginalType>,
-        pub data: String
+    pub struct Header {
+        pub name: String,
+        pub header_type: String,
+        pub description: Option<String>,
+        pub deprecation: Option<Deprecation>,
+        pub required: bool,
+        pub default: Option<String>
     }
 
-    impl Encodable for OriginalForm {
+    impl Encodable for Header {
         fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
-            e.emit_struct("original_form", 2, |e| {
-                try!(e.emit_struct_field("type", 0, |e| self.original_type.encode(e)));
-                try!(e.emit_struct_field("data", 1, |e| self.data.encode(e)));
+            e.emit_struct("header", 6, |e| {
+                try!(e.emit_struct_field("name", 0, |e| self.name.encode(e)));
+                try!(e.emit_struct_field("type", 1, |e| self.header_type.encode(e)));
+                try!(e.emit_struct_field("description", 2, |e| self.description.encode(e)));
+                try!(e.emit_struct_field("deprecation", 3, |e| self.deprecation.encode(e)));
+                try!(e.emit_struct_field("required", 4, |e| self.required.encode(e)));
+                try!(e.emit_struct_field("default", 5, |e| self.default.encode(e)));
                 Ok(())
             })
         }
     }
 
-    impl Decodable for OriginalForm {
+    impl Decodable for Header {
         fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
-            d.read_struct("original_form", 2, |d| {
-                let original_type: Option<OriginalType> = try!(
-                    d.read_struct_field("type", 0, Option::<OriginalType>::decode));
-                let data: String = try!(
-                    d.read_struct_field("data", 1, |d| d.read_str()));
-                Ok(OriginalForm {
-                    original_type: original_type,
-                    data: data
+            d.read_struct("header", 6, |d| {
+                Ok(Header {
+                    name: try!(d.read_struct_field("name", 0, |d| d.read_str())),
+                    header_type: try!(d.read_struct_field("type", 1, |d| d.read_str())),
+                    description: try!(d.read_struct_field("description", 2, Option::<String>::decode)),
+                    deprecation: try!(d.read_struct_field("deprecation", 3, Option::<Deprecation>::decode)),
+                    required: try!(read_required(d, 4)),
+                    default: try!(d.read_struct_field("default", 5, Option::<String>::decode))
                 })
             })
         }
     }
 
     /**
-     * Allows a user to change their password with authentication from a token.
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct PasswordReset {
-        pub token: String,
-        pub password: String
-    }
-
-    /**
-     * Create a password reset request - e.g. an email containing a one time URL to
-     * change a password
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct PasswordResetRequest {
-        pub email: String
-    }
-
-    /**
-     * On a successful password reset, return some metadata about the user modified.
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct PasswordResetSuccess {
-        pub user_guid: uuid::Uuid
-    }
-
-    /**
-     * Represents a reference to another model.
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct Reference {
-        pub guid: uuid::Uuid,
-        pub key: String
-    }
-
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct ReferenceGuid {
-        pub guid: uuid::Uuid
-    }
-
-    /**
-     * Represents a user that is currently subscribed to a publication
+     * An import is used to declare a dependency on another application. This allows
+     * you to reference the models and or enums from that application in your own app.
      */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct Subscription {
-        pub guid: uuid::Uuid,
+    #[derive(RustcEncodable)]
+    pub struct Import {
+        pub uri: String,
+        pub namespace: String,
         pub organization: Organization,
-        pub user: User,
-        pub publication: Publication
-    }
-
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct SubscriptionForm {
-        pub organization_key: String,
-        pub user_guid: uuid::Uuid,
-        pub publication: Publication
+        pub application: Application,
+        pub version: String,
+        pub enums: Vec<String>,
+        pub models: Vec<String>
     }
 
-    /**
-     * A token gives a user access to the API.
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct Token {
-        pub guid: uuid::Uuid,
-        pub user: User,
-        pub masked_token: String,
-        pub description: Option<String>,
-        pub audit: Audit
+    impl Decodable for Import {
+        fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
+            d.read_struct("import", 7, |d| {
+                Ok(Import {
+                    uri: try!(d.read_struct_field("uri", 0, |d| d.read_str())),
+                    namespace: try!(d.read_struct_field("namespace", 1, |d| d.read_str())),
+                    organization: try!(d.read_struct_field("organization", 2, Organization::decode)),
+                    application: try!(d.read_struct_field("application", 3, Application::decode)),
+                    version: try!(d.read_struct_field("version", 4, |d| d.read_str())),
+                    enums: try!(read_list(d, "enums", 5)),
+                    models: try!(read_list(d, "models", 6))
+                })
+            })
+        }
     }
 
+    /**
+     * General metadata about this service
+     */
     #[derive(RustcEncodable, RustcDecodable)]
-    pub struct TokenForm {
-        pub user_guid: uuid::Uuid,
-        pub description: Option<String>
+    pub struct Info {
+        pub license: Option<License>,
+        pub contact: Option<Contact>
     }
 
     /**
-     * A user is a top level person interacting with the api doc server.
+     * Describes the software license contact for this service
      */
     #[derive(RustcEncodable, RustcDecodable)]
-    pub struct User {
-        pub guid: uuid::Uuid,
-        pub email: String,
-        pub nickname: String,
-        pub name: Option<String>
+    pub struct License {
+        pub name: String,
+        pub url: Option<String>
     }
 
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct UserForm {
-        pub email: String,
-        pub password: String,
-        pub nickname: Option<String>,
-        pub name: Option<String>
+    #[derive(RustcEncodable)]
+    pub struct Model {
+        pub name: String,
+        pub plural: String,
+        pub description: Option<String>,
+        pub deprecation: Option<Deprecation>,
+        pub fields: Vec<Field>
     }
 
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct UserUpdateForm {
-        pub email: String,
-        pub nickname: String,
-        pub name: Option<String>
+    impl Decodable for Model {
+        fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
+            d.read_struct("model", 5, |d| {
+                Ok(Model {
+                    name: try!(d.read_struct_field("name", 0, |d| d.read_str())),
+                    plural: try!(d.read_struct_field("plural", 1, |d| d.read_str())),
+                    description: try!(d.read_struct_field("description", 2, Option::<String>::decode)),
+                    deprecation: try!(d.read_struct_field("deprecation", 3, Option::<Deprecation>::decode)),
+                    fields: try!(read_list(d, "fields", 4))
+                })
+            })
+        }
     }
 
-    /**
-     * Used only to validate json files - used as a resource where http status code
-     * defines success
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct Validation {
-        pub valid: bool,
-        pub errors: Vec<String>
+    #[derive(RustcEncodable)]
+    pub struct Operation {
+        pub method: Method,
+        pub path: String,
+        pub description: Option<String>,
+        pub deprecation: Option<Deprecation>,
+        pub body: Option<Body>,
+        pub parameters: Vec<Parameter>,
+        pub responses: Vec<Response>
     }
 
-    /**
-     * Represents a unique version of the application.
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct Version {
-        pub guid: uuid::Uuid,
-        pub organization: Reference,
-        pub application: Reference,
-        pub version: String,
-        pub original: Option<Original>,
-        // TODO pub service: com.gilt.apidoc.spec.v0.models.Service
+    impl Decodable for Operation {
+        fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
+            d.read_struct("operation", 7, |d| {
+                Ok(Operation {
+                    method: try!(d.read_struct_field("method", 0, Method::decode)),
+                    path: try!(d.read_struct_field("path", 1, |d| d.read_str())),
+                    description: try!(d.read_struct_field("description", 2, Option::<String>::decode)),
+                    deprecation: try!(d.read_struct_field("deprecation", 3, Option::<Deprecation>::decode)),
+                    body: try!(d.read_struct_field("body", 4, Option::<Body>::decode)),
+                    parameters: try!(read_list(d, "parameters", 5)),
+                    responses: try!(read_list(d, "responses", 6))
+                })
+            })
+        }
     }
 
     #[derive(RustcEncodable, RustcDecodable)]
-    pub struct VersionForm {
-        pub original_form: OriginalForm,
-        pub visibility: Option<Visibility>
+    pub struct Organization {
+        pub key: String
     }
 
-    /**
-     * Users can watch individual applications which enables features like receiving an
-     * email notification when there is a new version of an application.
-     */
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct Watch {
-        pub guid: uuid::Uuid,
-        pub user: User,
-        pub organization: Organization,
-        pub application: Application
+    pub struct Parameter {
+        pub name: String,
+        pub parameter_type: String,
+        pub location: ParameterLocation,
+        pub description: Option<String>,
+        pub deprecation: Option<Deprecation>,
+        pub required: bool,
+        pub default: Option<String>,
+        pub minimum: Option<i64>,
+        pub maximum: Option<i64>,
+        pub example: Option<String>
     }
 
-    #[derive(RustcEncodable, RustcDecodable)]
-    pub struct WatchForm {
-        pub user_guid: uuid::Uuid,
-        pub organization_key: String,
-        pub application_key: String
+    impl Encodable for Parameter {
+        fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
+            e.emit_struct("parameter", 10, |e| {
+                try!(e.emit_struct_field("name", 0, |e| self.name.encode(e)));
+                try!(e.emit_struct_field("type", 1, |e| self.parameter_type.encode(e)));
+                try!(e.emit_struct_field("location", 2, |e| self.location.encode(e)));
+                try!(e.emit_struct_field("description", 3, |e| self.description.encode(e)));
+                try!(e.emit_struct_field("deprecation", 4, |e| self.deprecation.encode(e)));
+                try!(e.emit_struct_field("required", 5, |e| self.required.encode(e)));
+                try!(e.emit_struct_field("default", 6, |e| self.default.encode(e)));
+                try!(e.emit_struct_field("minimum", 7, |e| self.minimum.encode(e)));
+                try!(e.emit_struct_field("maximum", 8, |e| self.maximum.encode(e)));
+                try!(e.emit_struct_field("example", 9, |e| self.example.encode(e)));
+                Ok(())
+            })
+        }
     }
 
-    pub enum OriginalType {
+    impl Decodable for Parameter {
+        fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
+            d.read_struct("parameter", 10, |d| {
+                Ok(Parameter {
+                    name: try!(d.read_struct_field("name", 0, |d| d.read_str())),
+                    parameter_type: try!(d.read_struct_field("type", 1, |d| d.read_str())),
+                    location: try!(d.read_struct_field("location", 2, ParameterLocation::decode)),
+                    description: try!(d.read_struct_field("description", 3, Option::<String>::decode)),
+                    deprecation: try!(d.read_struct_field("deprecation", 4, Option::<Deprecation>::decode)),
+                    required: try!(read_required(d, 5)),
+                    default: try!(d.read_struct_field("default", 6, Option::<String>::decode)),
+                    minimum: try!(d.read_struct_field("minimum", 7, Option::<i64>::decode)),
+                    maximum: try!(d.read_struct_field("maximum", 8, Option::<i64>::decode)),
+                    example: try!(d.read_struct_field("example", 9, Option::<String>::decode))
+                })
+            })
+        }
+    }
 
-        /**
-         * The original is in the api.json format
-         */
-        ApiJson,
+    pub struct Resource {
+        pub resource_type: String,
+        pub plural: String,
+        pub description: Option<String>,
+        pub deprecation: Option<Deprecation>,
+        pub operations: Vec<Operation>
+    }
 
-        /**
-         * The original in the swagger.json format
-         */
-        SwaggerJson,
+    impl Encodable for Resource {
+        fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
+            e.emit_struct("resource", 5, |e| {
+                try!(e.emit_struct_field("type", 0, |e| self.resource_type.encode(e)));
+                try!(e.emit_struct_field("plural", 1, |e| self.plural.encode(e)));
+                try!(e.emit_struct_field("description", 2, |e| self.description.encode(e)));
+                try!(e.emit_struct_field("deprecation", 3, |e| self.deprecation.encode(e)));
+                try!(e.emit_struct_field("operations", 4, |e| self.operations.encode(e)));
+                Ok(())
+            })
+        }
+    }
 
-        /**
-         * The original is in Avro Idl format
-         */
-        AvroIdl,
+    impl Decodable for Resource {
+        fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
+            d.read_struct("resource", 5, |d| {
+                Ok(Resource {
+                    resource_type: try!(d.read_struct_field("type", 0, |d| d.read_str())),
+                    plural: try!(d.read_struct_field("plural", 1, |d| d.read_str())),
+                    description: try!(d.read_struct_field("description", 2, Option::<String>::decode)),
+                    deprecation: try!(d.read_struct_field("deprecation", 3, Option::<Deprecation>::decode)),
+                    operations: try!(read_list(d, "operations", 4))
+                })
+            })
+        }
+    }
 
-        /**
-         * UNDEFINED captures values that are sent either in error or
-         * that were added by the server after this library was
-         * generated. We want to make it easy and obvious for users of
-         * this library to handle this case gracefully.
-         *
-         * We use all CAPS for the variable name to avoid collisions
-         * with the camel cased values above.
-         */
-        UNDEFINED(String)
+    pub struct Response {
+        pub code: i64,
+        pub response_type: String,
+        pub description: Option<String>,
+        pub deprecation: Option<Deprecation>
     }
 
-    impl Encodable for OriginalType {
+    impl Encodable for Response {
         fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
-            match self {
-                &OriginalType::ApiJson => e.emit_str("api_json"),
-                &OriginalType::SwaggerJson => e.emit_str("swagger_json"),
-                &OriginalType::AvroIdl => e.emit_str("avro_idl"),
-                &OriginalType::UNDEFINED(ref value) => e.emit_str(&value)
-            }
+            e.emit_struct("response", 4, |e| {
+                try!(e.emit_struct_field("code", 0, |e| self.code.encode(e)));
+                try!(e.emit_struct_field("type", 1, |e| self.response_type.encode(e)));
+                try!(e.emit_struct_field("description", 2, |e| self.description.encode(e)));
+                try!(e.emit_struct_field("deprecation", 3, |e| self.deprecation.encode(e)));
+                Ok(())
+            })
         }
     }
 
-    impl Decodable for OriginalType {
+    impl Decodable for Response {
         fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
-            d.read_str().map(|value| {
-                match &value[..] {
-                    "api_json" => OriginalType::ApiJson,
-                    "swagger_json" => OriginalType::SwaggerJson,
-                    "avro_idl" => OriginalType::AvroIdl,
-                    _ => OriginalType::UNDEFINED(value)
-                }
+            d.read_struct("response", 4, |d| {
+                Ok(Response {
+                    code: try!(d.read_struct_field("code", 0, |d| d.read_i64())),
+                    response_type: try!(d.read_struct_field("type", 1, |d| d.read_str())),
+                    description: try!(d.read_struct_field("description", 2, Option::<String>::decode)),
+                    deprecation: try!(d.read_struct_field("deprecation", 3, Option::<Deprecation>::decode))
+                })
             })
         }
     }
 
-    /**
-     * A publication represents something that a user can subscribe to. An example
-     * would be subscribing to an email alert whenever a new version of an application
-     * is created.
-     */
-    pub enum Publication {
-
-        /**
-         * For organizations for which I am an administrator, email me whenever a user
-         * applies to join the org.
-         */
-        MembershipRequestsCreate,
-
-        /**
-         * For organizations for which I am a member, email me whenever a user joins the
-         * org.
-         */
-        MembershipsCreate,
+    #[derive(RustcEncodable)]
+    pub struct Service {
+        pub apidoc: Apidoc,
+        pub name: String,
+        pub organization: Organization,
+        pub application: Application,
+        pub namespace: String,
+        pub version: String,
+        pub base_url: Option<String>,
+        pub description: Option<String>,
+        pub info: Option<Info>,
+        pub headers: Vec<Header>,
+        pub imports: Vec<Import>,
+        pub enums: Vec<Enum>,
+        pub models: Vec<Model>,
+        pub resources: Vec<Resource>
+    }
 
-        /**
-         * For organizations for which I am a member, email me whenever an application is
-         * created.
-         */
-        ApplicationsCreate,
+    impl Decodable for Service {
+        fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
+            d.read_struct("service", 14, |d| {
+                Ok(Service {
+                    apidoc: try!(d.read_struct_field("apidoc", 0, Apidoc::decode)),
+                    name: try!(d.read_struct_field("name", 1, |d| d.read_str())),
+                    organization: try!(d.read_struct_field("organization", 2, Organization::decode)),
+                    application: try!(d.read_struct_field("application", 3, Application::decode)),
+                    namespace: try!(d.read_struct_field("namespace", 4, |d| d.read_str())),
+                    version: try!(d.read_struct_field("version", 5, |d| d.read_str())),
+                    base_url: try!(d.read_struct_field("base_url", 6, Option::<String>::decode)),
+                    description: try!(d.read_struct_field("description", 7, Option::<String>::decode)),
+                    info: try!(d.read_struct_field("info", 8, Option::<Info>::decode)),
+                    headers: try!(read_list(d, "headers", 9)),
+                    imports: try!(read_list(d, "imports", 10)),
+                    enums: try!(read_list(d, "enums", 11)),
+                    models: try!(read_list(d, "models", 12)),
+                    resources: try!(read_list(d, "resources", 13))
+                })
+            })
+        }
+    }
 
-        /**
-         * For applications that I watch, email me whenever a version is created.
-         */
-        VersionsCreate,
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Method {
+        Get,
+        Post,
+        Put,
+        Patch,
+        Delete,
+        Head,
+        Connect,
+        Options,
+        Trace,
 
         /**
          * UNDEFINED captures values that are sent either in error or
@@ -632,52 +2028,53 @@ pub mod models {
         UNDEFINED(String)
     }
 
-    impl Encodable for Publication {
-        fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
+    impl std::fmt::Display for Method {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
             match self {
-                &Publication::MembershipRequestsCreate => e.emit_str("membership_requests.create"),
-                &Publication::MembershipsCreate => e.emit_str("memberships.create"),
-                &Publication::ApplicationsCreate => e.emit_str("applications.create"),
-                &Publication::VersionsCreate => e.emit_str("versions.create"),
-                &Publication::UNDEFINED(ref value) => e.emit_str(value)
+                &Method::Get => f.write_str("GET"),
+                &Method::Post => f.write_str("POST"),
+                &Method::Put => f.write_str("PUT"),
+                &Method::Patch => f.write_str("PATCH"),
+                &Method::Delete => f.write_str("DELETE"),
+                &Method::Head => f.write_str("HEAD"),
+                &Method::Connect => f.write_str("CONNECT"),
+                &Method::Options => f.write_str("OPTIONS"),
+                &Method::Trace => f.write_str("TRACE"),
+                &Method::UNDEFINED(ref value) => f.write_str(value)
             }
         }
     }
 
-    impl Decodable for Publication {
+    impl Encodable for Method {
+        fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
+            e.emit_str(&self.to_string())
+        }
+    }
+
+    impl Decodable for Method {
         fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
             d.read_str().map(|value| {
                 match &value[..] {
-                    "membership_requests.create" => Publication::MembershipRequestsCreate,
-                    "memberships.create" => Publication::MembershipsCreate,
-                    "applications.create" => Publication::ApplicationsCreate,
-                    "versions.create" => Publication::VersionsCreate,
-                    _ => Publication::UNDEFINED(value)
+                    "GET" => Method::Get,
+                    "POST" => Method::Post,
+                    "PUT" => Method::Put,
+                    "PATCH" => Method::Patch,
+                    "DELETE" => Method::Delete,
+                    "HEAD" => Method::Head,
+                    "CONNECT" => Method::Connect,
+                    "OPTIONS" => Method::Options,
+                    "TRACE" => Method::Trace,
+                    _ => Method::UNDEFINED(value)
                 }
             })
         }
     }
 
-    /**
-     * Controls who is able to view this version
-     */
-    #[derive(Clone, Debug)]
-    pub enum Visibility {
-
-        /**
-         * Only the creator can view this application
-         */
-        User,
-
-        /**
-         * Any member of the organization can view this application
-         */
-        Organization,
-
-        /**
-         * Anybody, including non logged in users, can view this application
-         */
-        Public,
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum ParameterLocation {
+        Path,
+        Query,
+        Form,
 
         /**
          * UNDEFINED captures values that are sent either in error or
@@ -691,47 +2088,163 @@ pub mod models {
         UNDEFINED(String)
     }
 
-    impl Visibility {
-        pub fn valid(&self) -> Result<&Self, &Self> {
-            match self {
-                &Visibility::UNDEFINED(_) => Err(self),
-                _ => Ok(self)
-            }
-        }
-    }
-
-    impl std::fmt::Display for Visibility {
+    impl std::fmt::Display for ParameterLocation {
         fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
             match self {
-                &Visibility::User => f.write_str("user"),
-                &Visibility::Organization => f.write_str("organization"),
-                &Visibility::Public => f.write_str("public"),
-                &Visibility::UNDEFINED(ref value) => f.write_str(value)
+                &ParameterLocation::Path => f.write_str("Path"),
+                &ParameterLocation::Query => f.write_str("Query"),
+                &ParameterLocation::Form => f.write_str("Form"),
+                &ParameterLocation::UNDEFINED(ref value) => f.write_str(value)
             }
         }
     }
 
-    impl ::rustc_serialize::Encodable for Visibility {
+    impl Encodable for ParameterLocation {
         fn encode<E: Encoder>(&self, e: &mut E) -> Result<(), E::Error> {
-            match self {
-                &Visibility::User => e.emit_str("user"),
-                &Visibility::Organization => e.emit_str("organization"),
-                &Visibility::Public => e.emit_str("public"),
-                &Visibility::UNDEFINED(ref value) => e.emit_str(value)
-            }
+            e.emit_str(&self.to_string())
         }
     }
 
-    impl ::rustc_serialize::Decodable for Visibility {
+    impl Decodable for ParameterLocation {
         fn decode<D: Decoder>(d: &mut D) -> Result<Self, D::Error> {
             d.read_str().map(|value| {
                 match &value[..] {
-                    "user" => Visibility::User,
-                    "organization" => Visibility::Organization,
-                    "public" => Visibility::Public,
-                    _ => Visibility::UNDEFINED(value)
+                    "Path" => ParameterLocation::Path,
+                    "Query" => ParameterLocation::Query,
+                    "Form" => ParameterLocation::Form,
+                    _ => ParameterLocation::UNDEFINED(value)
                 }
             })
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rustc_serialize::json;
+    use super::models::Version;
+
+    // A version as returned by GET /:orgKey/:applicationKey/:version,
+    // trimmed to a single model and resource.
+    const VERSION: &'static str = r#"{
+        "guid": "1dfe5f05-e5ed-4c4d-9a6b-23ce1d4d9f2a",
+        "organization": { "guid": "7d4ac8d5-3b7a-4cde-8b82-29c5d0a1f0a1", "key": "gilt" },
+        "application": { "guid": "5bd1e8e2-4c7d-4a36-9fb7-3b7f4e1e2d44", "key": "apidoc-api" },
+        "version": "0.9.6",
+        "original": {
+            "type": "api_json",
+            "data": "{ \"name\": \"apidoc api\" }"
+        },
+        "service": {
+            "apidoc": { "version": "0.9.6" },
+            "name": "apidoc api",
+            "organization": { "key": "gilt" },
+            "application": { "key": "apidoc-api" },
+            "namespace": "com.gilt.apidoc.api.v0",
+            "version": "0.9.6",
+            "base_url": "http://api.apidoc.me",
+            "description": "Host API documentation for applications providing REST APIs.",
+            "headers": [],
+            "imports": [
+                {
+                    "uri": "http://www.apidoc.me/gilt/apidoc-spec/0.8.18/service.json",
+                    "namespace": "com.gilt.apidoc.spec.v0",
+                    "organization": { "key": "gilt" },
+                    "application": { "key": "apidoc-spec" },
+                    "version": "0.8.18",
+                    "enums": ["method", "parameter_location"],
+                    "models": ["service"]
+                }
+            ],
+            "enums": [
+                {
+                    "name": "visibility",
+                    "plural": "visibilities",
+                    "description": "Controls who is able to view this version",
+                    "values": [
+                        { "name": "user", "description": "Only the creator can view this application" },
+                        { "name": "public" }
+                    ]
+                }
+            ],
+            "models": [
+                {
+                    "name": "healthcheck",
+                    "plural": "healthchecks",
+                    "fields": [
+                        { "name": "status", "type": "string", "required": true, "example": "healthy" }
+                    ]
+                }
+            ],
+            "resources": [
+                {
+                    "type": "healthcheck",
+                    "plural": "healthchecks",
+                    "operations": [
+                        {
+                            "method": "GET",
+                            "path": "/_internal_/healthcheck",
+                            "parameters": [
+                                { "name": "verbose", "type": "boolean", "location": "Query", "required": false }
+                            ],
+                            "responses": [
+                                { "code": 200, "type": "healthcheck" }
+                            ]
+                        }
+                    ]
+                }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn decodes_version_with_service() {
+        let version: Version = json::decode(VERSION).unwrap();
+        assert_eq!(version.version, "0.9.6");
+        let service = version.service.unwrap();
+        assert_eq!(service.name, "apidoc api");
+        assert_eq!(service.imports[0].models, vec!["service".to_string()]);
+        assert_eq!(service.enums[0].values.len(), 2);
+        assert_eq!(service.models[0].fields[0].field_type, "string");
+        let operation = &service.resources[0].operations[0];
+        assert_eq!(operation.method.to_string(), "GET");
+        assert_eq!(operation.parameters[0].location.to_string(), "Query");
+        assert!(!operation.parameters[0].required);
+        assert_eq!(operation.responses[0].code, 200);
+    }
+
+    #[test]
+    fn decodes_service_without_lists() {
+        let version: Version = json::decode(r#"{
+            "guid": "1dfe5f05-e5ed-4c4d-9a6b-23ce1d4d9f2a",
+            "organization": { "guid": "7d4ac8d5-3b7a-4cde-8b82-29c5d0a1f0a1", "key": "gilt" },
+            "application": { "guid": "5bd1e8e2-4c7d-4a36-9fb7-3b7f4e1e2d44", "key": "apidoc-api" },
+            "version": "0.9.6",
+            "service": {
+                "apidoc": { "version": "0.9.6" },
+                "name": "apidoc api",
+                "organization": { "key": "gilt" },
+                "application": { "key": "apidoc-api" },
+                "namespace": "com.gilt.apidoc.api.v0",
+                "version": "0.9.6",
+                "models": [{ "name": "healthcheck", "plural": "healthchecks" }]
+            }
+        }"#).unwrap();
+        let service = version.service.unwrap();
+        assert!(service.headers.is_empty());
+        assert!(service.resources.is_empty());
+        assert!(service.models[0].fields.is_empty());
+    }
+
+    #[test]
+    fn decodes_version_without_service() {
+        let version: Version = json::decode(r#"{
+            "guid": "1dfe5f05-e5ed-4c4d-9a6b-23ce1d4d9f2a",
+            "organization": { "guid": "7d4ac8d5-3b7a-4cde-8b82-29c5d0a1f0a1", "key": "gilt" },
+            "application": { "guid": "5bd1e8e2-4c7d-4a36-9fb7-3b7f4e1e2d44", "key": "apidoc-api" },
+            "version": "0.9.6"
+        }"#).unwrap();
+        assert!(version.service.is_none());
+        assert!(version.original.is_none());
+    }
+}
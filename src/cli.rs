@@ -1,24 +1,42 @@
+extern crate chrono;
 extern crate hyper;
 extern crate toml;
 
 use apidoc::client;
 use apidoc::models;
+use apidoc::spec;
+use diff;
+use lint;
+use locate;
+use report;
+use sha256;
 use rustc_serialize::Decodable;
 use rustc_serialize::Decoder;
 use rustc_serialize::json;
 use rustc_serialize::json::Json;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
 use std::error;
 use std::error::Error;
 use std::fmt;
 use std::fmt::Display;
 use std::fmt::Formatter;
+use std::fs;
 use std::fs::File;
+use std::io;
 use std::io::prelude::*;
 use std::io::stderr;
+use std::io::stdin;
 use std::io::stdout;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
 use std::result::Result as StdResult;
 use std::str::FromStr;
+use self::chrono::UTC;
 use self::hyper::client::Response;
+use self::hyper::error::Error as HttpError;
+use self::hyper::error::Result as HttpResult;
 use self::hyper::status::StatusCode;
 
 
@@ -100,17 +118,163 @@ impl CliError {
     }
 }
 
-// TODO implement support for parsing
-// some kind of project manifest file,
-// resolving dependencies, and generating
-// code for them as described in said file.
+// A project manifest (`Apidoc.toml`) lists the revisions
+// a project depends on and, for each of them, the file that
+// the code for each generator should be written to, e.g.
 //
-// Thinking that this would be the default
-// mode of the generate command (without args).
-pub struct Project<'a> {
-    dependencies: Vec<Revision<'a>>
+//     [dependencies."gilt/apidoc-api:0.8.18"]
+//     ning_1_9_client = "apidoc-client/src/main/scala/Apidoc.scala"
+//
+// Output paths are relative to the directory containing the manifest.
+pub struct Project {
+    dependencies: Vec<Dependency>
+}
+
+pub struct Dependency {
+    tag: String,
+    outputs: Vec<(String, PathBuf)>
+}
+
+impl Project {
+    pub fn load(path: &str) -> Result<Project, CliError> {
+        let value = try!(load_toml(path, "manifest"));
+        // A misspelt `dependencies` would otherwise go unnoticed.
+        if let Some(table) = value.as_table() {
+            for key in table.keys() {
+                if key != "dependencies" {
+                    return Err(CliError {
+                        desc: format!("unknown key `{}` in manifest at `{}`", key, path)
+                    });
+                }
+            }
+        }
+        let root = Path::new(path).parent().unwrap_or(Path::new(""));
+        let mut dependencies = Vec::new();
+        let tables = match value.lookup("dependencies") {
+            Some(deps) => cli_opt!(
+                deps.as_table(),
+                "`dependencies` is not a table in manifest at `{}`",
+                path).clone(),
+            None => toml::Table::new()
+        };
+        for (tag, outputs) in tables.into_iter() {
+            try!(Revision::from_str(&tag[..]));
+            let outputs = cli_opt!(
+                outputs.as_table(),
+                "dependency `{}` is not a table in manifest at `{}`",
+                tag, path);
+            let mut dependency = Dependency { tag: tag.clone(), outputs: Vec::new() };
+            for (generator, output) in outputs.iter() {
+                let output = cli_opt!(
+                    output.as_str(),
+                    "output for `{}` in `{}` is not a string in manifest at `{}`",
+                    generator, tag, path);
+                dependency.outputs.push((generator.clone(), root.join(output)));
+            }
+            dependencies.push(dependency);
+        }
+        Ok(Project { dependencies: dependencies })
+    }
+}
+
+impl Dependency {
+    fn revision<'a>(&'a self) -> Result<Revision<'a>, CliError> {
+        Revision::from_str(&self.tag[..])
+    }
+}
+
+// The lockfile (`Apidoc.lock`) records, for every generator of every
+// dependency in the manifest, the concrete version that was resolved,
+// the generator that produced the code and a hash of that code.
+#[derive(RustcEncodable, RustcDecodable)]
+pub struct Lockfile {
+    dependencies: Vec<LockedDependency>
+}
+
+#[derive(RustcEncodable, RustcDecodable)]
+pub struct LockedDependency {
+    tag: String,
+    version: String,
+    generator: String,
+    generator_guid: String,
+    hash: String
+}
+
+impl Lockfile {
+    pub fn load(path: &Path) -> Result<Lockfile, CliError> {
+        let path = cli_opt!(path.to_str(), "invalid lockfile path: {}", path.display());
+        let value = try!(load_toml(path, "lockfile"));
+        let mut decoder = toml::Decoder::new(value);
+        Ok(cli_try!(
+            Lockfile::decode(&mut decoder),
+            "failed to decode lockfile at `{}`: {}",
+            path))
+    }
+
+    // The lockfile to honour when syncing in `mode`, which is empty when
+    // updating, so that every version is resolved again.
+    fn previous(path: &Path, mode: SyncMode) -> Result<Lockfile, CliError> {
+        if mode != SyncMode::Update && fs::metadata(path).is_ok() {
+            Lockfile::load(path)
+        } else {
+            Ok(Lockfile { dependencies: Vec::new() })
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), CliError> {
+        write_file(path, &toml::encode_str(self)[..])
+    }
+
+    fn find(&self, tag: &str, generator: &str) -> Option<&LockedDependency> {
+        self.dependencies.iter().find(|locked| {
+            locked.tag == tag && locked.generator == generator
+        })
+    }
+
+    fn version(&self, tag: &str) -> Option<&str> {
+        self.dependencies.iter()
+            .find(|locked| locked.tag == tag)
+            .map(|locked| &locked.version[..])
+    }
+}
+
+impl LockedDependency {
+    // Fails unless `code`, with the given hash, is what was generated
+    // when this was locked, e.g. because the generator has changed since.
+    fn verify(&self, code: &models::Code, hash: &str) -> CliResult<()> {
+        if code.generator.guid.to_string() != self.generator_guid {
+            Err(CliError {
+                desc: format!(
+                    "generator {} for {} differs from the one in the lockfile; \
+                     run `apidoc update` to use it",
+                    self.generator, self.tag)
+            })
+        } else if hash != self.hash {
+            Err(CliError {
+                desc: format!(
+                    "code generated by {} for {} differs from the hash in the lockfile; \
+                     run `apidoc update` to accept it",
+                    self.generator, self.tag)
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+// What `sync_project` does with the code it generates.
+#[derive(Clone, Copy, PartialEq)]
+enum SyncMode {
+    Generate,
+    Update,
+    Check
 }
 
+fn content_hash(source: &str) -> String {
+    format!("sha256:{}", sha256::hex_digest(source.as_bytes()))
+}
+
+#[derive(Clone, Copy)]
 pub struct Repo<'a>(&'a str, &'a str);
 
 impl<'a> Repo<'a> {
@@ -130,6 +294,7 @@ impl<'a> Repo<'a> {
     }
 }
 
+#[derive(Clone, Copy)]
 pub struct Revision<'a>(Repo<'a>, &'a str);
 
 impl <'a> Revision<'a> {
@@ -149,6 +314,7 @@ impl <'a> Revision<'a> {
     }
 }
 
+#[derive(Clone, Copy)]
 pub struct GenerateTarget<'a>(Revision<'a>, &'a str);
 
 impl <'a> GenerateTarget<'a> {
@@ -182,21 +348,7 @@ impl Display for CliError {
 
 impl Config {
     pub fn load(path: &str, profile_name: &str) -> Result<Config, CliError> {
-        let mut file = cli_try!(
-            File::open(path),
-            "failed to open config at `{}`: {}",
-            path);
-        let mut buf = String::new();
-        file.read_to_string(&mut buf).unwrap();
-        let result: Result<toml::Value, Vec<toml::ParserError>> = buf.parse();
-        let value: toml::Value = try!(result.map_err(|errs| {
-            let mut desc = String::new();
-            for err in errs {
-                desc.push_str(err.description());
-                desc.push('\n');
-            }
-            CliError { desc: desc }
-        }));
+        let value = try!(load_toml(path, "config"));
         let profile = cli_opt!(
             value.lookup(profile_name),
             "no profile found for {}",
@@ -206,6 +358,122 @@ impl Config {
     }
 }
 
+fn load_toml(path: &str, kind: &str) -> Result<toml::Value, CliError> {
+    let mut file = cli_try!(
+        File::open(path),
+        "failed to open {} at `{}`: {}",
+        kind, path);
+    let mut buf = String::new();
+    cli_try!(
+        file.read_to_string(&mut buf),
+        "failed reading {} at `{}`: {}",
+        kind, path);
+    let result: Result<toml::Value, Vec<toml::ParserError>> = buf.parse();
+    result.map_err(|errs| {
+        let mut desc = String::new();
+        for err in errs {
+            desc.push_str(err.description());
+            desc.push('\n');
+        }
+        CliError { desc: desc }
+    })
+}
+
+// The API URL and token of a profile, as far as the config and the
+// profile exist, for commands that can be run before it is complete.
+fn read_profile(path: &str, profile_name: &str) -> CliResult<(Option<String>, Option<String>)> {
+    if fs::metadata(path).is_err() {
+        return Ok((None, None));
+    }
+    let value = try!(load_toml(path, "config"));
+    let setting = |key: &str| {
+        value.lookup(profile_name)
+            .and_then(|profile| profile.lookup(key))
+            .and_then(|setting| setting.as_str())
+            .map(|setting| setting.to_string())
+    };
+    Ok((setting("api_url"), setting("token")))
+}
+
+// The API URL of a profile, if it has one.
+pub fn profile_api_url(path: &str, profile_name: &str) -> CliResult<Option<String>> {
+    Ok(try!(read_profile(path, profile_name)).0)
+}
+
+// Authenticates with an email address and password, creates a token
+// for the CLI and stores it in the profile named `profile_name` of the
+// config at `config_path`, creating the config if it does not exist.
+// The server is the one the profile points at, unless `api_url` is given.
+pub fn login(config_path: &str, profile_name: &str, api_url: Option<String>) -> CliResult<()> {
+    let (profile_url, profile_token) = try!(read_profile(config_path, profile_name));
+    // A token from an earlier login is only good for the server it was
+    // created on. Without one, the token requests below are sent without
+    // authorization, as authenticating returns no session to use; they
+    // rely on the server accepting them for the user that just did.
+    let token = match api_url {
+        Some(ref api_url) if Some(api_url) != profile_url.as_ref() => None,
+        _ => profile_token
+    };
+    let api_url = api_url.or(profile_url);
+    let mut cli = Cli::new(Config {
+        api_url: api_url.clone(),
+        token: token.unwrap_or(String::new())
+    });
+    let email = try!(cli.prompt("email:"));
+    let password = try!(cli.prompt_password("password:"));
+    let task = Authenticate { email: &email[..], password: &password[..] };
+    let user = match try!(task.fetch(&mut cli)) {
+        Ok(user) => user,
+        Err(errors) => return report_errors(&mut cli, errors)
+    };
+    let task = CreateToken {
+        form: models::TokenForm {
+            user_guid: user.guid,
+            description: Some("apidoc CLI".to_string())
+        }
+    };
+    let token = match try!(task.fetch(&mut cli)) {
+        Ok(token) => token,
+        Err(errors) => return report_errors(&mut cli, errors)
+    };
+    let task = ShowCleartextToken { guid: token.guid.to_string() };
+    let cleartext = match try!(task.fetch(&mut cli)) {
+        Ok(cleartext) => cleartext,
+        Err(errors) => return report_errors(&mut cli, errors)
+    };
+    try!(save_profile(config_path, profile_name, &cleartext.token[..], api_url));
+    err!(cli, "logged in as {}, saved profile `{}` to {}", user.email, profile_name, config_path);
+    Ok(())
+}
+
+// Sets the token (and API URL, if given) of a profile in the config,
+// leaving any other profiles and settings as they were.
+fn save_profile(
+    path: &str,
+    profile_name: &str,
+    token: &str,
+    api_url: Option<String>
+) -> CliResult<()> {
+    let mut root = if fs::metadata(path).is_ok() {
+        match try!(load_toml(path, "config")) {
+            toml::Value::Table(table) => table,
+            _ => toml::Table::new()
+        }
+    } else {
+        toml::Table::new()
+    };
+    let mut profile = match root.remove(profile_name) {
+        Some(toml::Value::Table(table)) => table,
+        _ => toml::Table::new()
+    };
+    profile.insert("token".to_string(), toml::Value::String(token.to_string()));
+    if let Some(api_url) = api_url {
+        profile.insert("api_url".to_string(), toml::Value::String(api_url));
+    }
+    root.insert(profile_name.to_string(), toml::Value::Table(profile));
+    write_private_file(Path::new(path), &toml::Value::Table(root).to_string()[..])
+}
+
 pub struct Cli {
     config: Config,
     out: Box<Write>,
@@ -221,199 +489,2727 @@ impl Cli {
         }
     }
 
-    pub fn check(&mut self, path: &str) -> Result<(), CliError> {
-        let task = Check { path: path };
+    // A CLI without a token, for the few requests that
+    // can be made before a user has one, like signing up.
+    pub fn anonymous(api_url: Option<String>) -> Cli {
+        Cli::new(Config { api_url: api_url, token: String::new() })
+    }
+
+    pub fn check(
+        &mut self,
+        path: &str,
+        original_type: Option<&models::OriginalType>,
+        offline: bool,
+        format: report::Format
+    ) -> Result<(), CliError> {
+        let mut task = Check {
+            path: path,
+            input: String::new(),
+            format: format,
+            warnings: Vec::new()
+        };
+        match self.validate(&mut task, original_type, offline) {
+            Ok(result) => task.handle_result(self, result),
+            Err(err) => task.fail(self, err)
+        }
+    }
+
+    fn validate(
+        &mut self,
+        task: &mut Check,
+        original_type: Option<&models::OriginalType>,
+        offline: bool
+    ) -> CliResult<<Check as Task>::Result> {
+        task.input = try!(read_file(task.path));
+        let detected = detect_original_type(task.path, &task.input[..]);
+        // The validations endpoint works out the type of the input by
+        // itself, so an explicit type is only checked against the input.
+        if let Some(expected) = original_type {
+            cli_try!(expected.valid(), "invalid type: {}");
+            if let Some(ref detected) = detected {
+                if detected != expected {
+                    return Err(CliError {
+                        desc: format!("`{}` looks like {}, not {}", task.path, detected, expected)
+                    });
+                }
+            }
+        }
+        match original_type.or(detected.as_ref()) {
+            Some(&models::OriginalType::ApiJson) => (),
+            Some(other) if offline => {
+                return Err(CliError {
+                    desc: format!("only api_json can be checked offline, not {}", other)
+                });
+            },
+            Some(_) => return task.fetch(self),
+            // Let the server have its say about input of unknown type,
+            // unless there is no server to ask.
+            None if !offline => return task.fetch(self),
+            None => ()
+        }
+        let errors = lint::check(&task.input[..]);
+        if offline {
+            let validation = models::Validation { valid: errors.is_empty(), errors: errors };
+            Ok(if validation.valid { Ok(validation) } else { Err(validation) })
+        } else {
+            // The server has the final say, so anything found locally
+            // is only reported alongside its result.
+            task.warnings = errors;
+            task.fetch(self)
+        }
+    }
+
+    pub fn generate(&mut self, tag: &str, output: Option<&str>) -> Result<(), CliError> {
+        let task = Generate {
+            target: cli_try!(GenerateTarget::from_str(tag)),
+            output: output.map(Path::new),
+            check: false
+        };
         task.run(self)
     }
 
-    pub fn generate(&mut self, tag: &str) -> Result<(), CliError> {
+    pub fn check_generated(&mut self, tag: &str, path: &str) -> Result<(), CliError> {
         let task = Generate {
-            target: cli_try!(GenerateTarget::from_str(tag))
+            target: cli_try!(GenerateTarget::from_str(tag)),
+            output: Some(Path::new(path)),
+            check: true
         };
         task.run(self)
     }
 
+    pub fn generate_project(&mut self, manifest: &str) -> Result<(), CliError> {
+        self.sync_project(manifest, SyncMode::Generate)
+    }
+
+    pub fn update_project(&mut self, manifest: &str) -> Result<(), CliError> {
+        self.sync_project(manifest, SyncMode::Update)
+    }
+
+    pub fn check_project(&mut self, manifest: &str) -> Result<(), CliError> {
+        self.sync_project(manifest, SyncMode::Check)
+    }
+
+    // Generates code for every dependency in the manifest and records
+    // what was generated in the lockfile next to it. Unless updating,
+    // versions pinned by an existing lockfile are reused instead of
+    // being resolved again, so that `latest` means the same thing for
+    // everybody until the lockfile is updated. Code generated for a
+    // locked version must also come from the locked generator and match
+    // the locked hash. When checking, the code is compared against what
+    // is on disk and nothing is written.
+    fn sync_project(&mut self, manifest: &str, mode: SyncMode) -> Result<(), CliError> {
+        let project = try!(Project::load(manifest));
+        let lock_path = Path::new(manifest).with_extension("lock");
+        let previous = try!(Lockfile::previous(&lock_path, mode));
+        let mut lock = Lockfile { dependencies: Vec::new() };
+        let mut generated = Vec::new();
+        for dependency in project.dependencies.iter() {
+            let revision = try!(dependency.revision());
+            // Every generator of a dependency uses the same version, so
+            // `latest` is resolved at most once per dependency.
+            let version = match previous.version(&dependency.tag[..]) {
+                Some(version) => version.to_string(),
+                None => try!(self.resolve_version(revision))
+            };
+            let Revision(repo, _) = revision;
+            for &(ref generator, ref path) in dependency.outputs.iter() {
+                let task = Generate {
+                    target: GenerateTarget(Revision(repo, &version[..]), &generator[..]),
+                    output: Some(path.as_ref()),
+                    check: mode == SyncMode::Check
+                };
+                let code = match try!(task.fetch(self)) {
+                    Ok(code) => code,
+                    Err(errors) => return report_errors(self, errors)
+                };
+                let hash = content_hash(&code.source[..]);
+                if let Some(locked) = previous.find(&dependency.tag[..], &generator[..]) {
+                    try!(locked.verify(&code, &hash[..]));
+                }
+                lock.dependencies.push(LockedDependency {
+                    tag: dependency.tag.clone(),
+                    version: version.clone(),
+                    generator: generator.clone(),
+                    generator_guid: code.generator.guid.to_string(),
+                    hash: hash
+                });
+                generated.push((path, code.source));
+            }
+        }
+        // Nothing is written until all of the code has been generated
+        // and verified, so that a failure part way through leaves the
+        // files and the lockfile as they were.
+        let mut stale = 0;
+        for (path, source) in generated.into_iter() {
+            if mode == SyncMode::Check {
+                if let Err(err) = check_file(self, path, &source[..]) {
+                    err!(self, "{}", err);
+                    stale += 1;
+                }
+            } else {
+                try!(write_file(path, &source[..]));
+                err!(self, "wrote {}", path.display());
+            }
+        }
+        if stale > 0 {
+            Err(CliError { desc: format!("{} generated file(s) out of date", stale) })
+        } else if mode == SyncMode::Check {
+            Ok(())
+        } else {
+            lock.save(&lock_path)
+        }
+    }
+
+    fn resolve_version(&mut self, revision: Revision) -> CliResult<String> {
+        let Revision(_, version) = revision;
+        if version != "latest" {
+            return Ok(version.to_string());
+        }
+        let Revision(Repo(org, app), _) = revision;
+        err!(self, "resolving version {}/{}:{}", org, app, version);
+        let task = ShowVersion { revision: revision };
+        match try!(task.fetch(self)) {
+            Ok(version) => Ok(version.version),
+            Err(errors) => report_errors(self, errors)
+        }
+    }
+
     pub fn push(
         &mut self,
         tag: &str,
         path: &str,
-        visibility: &models::Visibility
+        visibility: &models::Visibility,
+        original_type: Option<&models::OriginalType>
     ) -> Result<(), CliError> {
-        let task = Push { tag: tag, path: path, visibility: visibility };
-        task.run(self)
+        cli_try!(visibility.valid(), "invalid visiblity: {}");
+        if let Some(original_type) = original_type {
+            cli_try!(original_type.valid(), "invalid type: {}");
+        }
+        let input = try!(read_file(path));
+        let original_type = original_type
+            .map(|original_type| original_type.clone())
+            .or_else(|| detect_original_type(path, &input[..]));
+        let task = Push {
+            revision: try!(Revision::from_str(tag)),
+            input: input,
+            original_type: original_type,
+            visibility: visibility
+        };
+        let result = match task.fetch(self) {
+            Ok(result) => result,
+            Err(err) => {
+                // The server didn't get to answer, or not understandably,
+                // so it may be to blame rather than the input. Whatever
+                // the health check finds is only added to the error.
+                if let Err(health) = self.ensure_healthy() {
+                    err!(self, "{}", health);
+                }
+                return Err(err);
+            }
+        };
+        task.handle_result(self, result)
     }
 
-    fn code(&self) -> client::Code {
-        let api_url = self.config.api_url.clone().unwrap_or(
-            "http://api.apidoc.me".to_string());
-        client::Code::new(api_url, self.config.token.clone())
+    pub fn health(&mut self) -> Result<(), CliError> {
+        let started = UTC::now();
+        let healthcheck = try!(self.healthcheck());
+        let elapsed = UTC::now() - started;
+        let api_url = self.api_url();
+        out!(self, "{}: {} ({} ms)", api_url, healthcheck.status, elapsed.num_milliseconds());
+        if healthcheck.status == "healthy" {
+            Ok(())
+        } else {
+            Err(CliError { desc: format!("server at {} is not healthy", api_url) })
+        }
     }
 
-    fn validations(&self) -> client::Validations {
-        let api_url = self.config.api_url.clone().unwrap_or(
-            "http://api.apidoc.me".to_string());
-        client::Validations::new(api_url)
+    // Fails unless the server is reachable and reports itself healthy,
+    // so that problems with the server can be reported as such along
+    // with the failure of whatever was being attempted.
+    fn ensure_healthy(&mut self) -> Result<(), CliError> {
+        let healthcheck = try!(self.healthcheck());
+        if healthcheck.status == "healthy" {
+            Ok(())
+        } else {
+            Err(CliError {
+                desc: format!("server at {} is not healthy: {}", self.api_url(), healthcheck.status)
+            })
+        }
     }
 
-    fn versions(&self) -> client::Versions {
-        let api_url = self.config.api_url.clone().unwrap_or(
-            "http://api.apidoc.me".to_string());
-        client::Versions::new(api_url, self.config.token.clone())
+    fn healthcheck(&mut self) -> CliResult<models::Healthcheck> {
+        match try!(Healthcheck.fetch(self)) {
+            Ok(healthcheck) => Ok(healthcheck),
+            Err(errors) => report_errors(self, errors)
+        }
     }
-}
 
-trait Task {
-    type Result;
+    pub fn list_applications(&mut self, org: &str) -> Result<(), CliError> {
+        let task = ListApplications { org: org };
+        task.run(self)
+    }
 
-    fn perform_request(&self, cli: &mut Cli) -> CliResult<Response>;
+    pub fn show_application(&mut self, tag: &str) -> Result<(), CliError> {
+        let task = ShowApplication { repo: try!(Repo::from_str(tag)) };
+        task.run(self)
+    }
 
-    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<Self::Result>;
+    pub fn create_application(
+        &mut self,
+        org: &str,
+        form: models::ApplicationForm
+    ) -> Result<(), CliError> {
+        cli_try!(form.visibility.valid(), "invalid visiblity: {}");
+        let task = CreateApplication { org: org, form: form };
+        task.run(self)
+    }
 
-    fn handle_result(&self, cli: &mut Cli, result: Self::Result) -> CliResult<()>;
+    pub fn delete_application(&mut self, tag: &str, confirmed: bool) -> Result<(), CliError> {
+        let repo = try!(Repo::from_str(tag));
+        if !confirmed && !try!(self.confirm(&format!("delete application {}?", tag)[..])) {
+            return Err(CliError { desc: "aborted".to_string() });
+        }
+        let task = DeleteApplication { repo: repo };
+        task.run(self)
+    }
 
-    fn run(&self, cli: &mut Cli) -> CliResult<()> {
-        let mut res = cli_try!(self.perform_request(cli), "HTTP request failed: {}");
-        let status = res.status;
-        let json = cli_try!(
-            Json::from_reader(&mut res),
-            "failed to parse HTTP response body as JSON (status was {}): {}",
-            status);
-        self.handle_result(cli, cli_try!(self.parse_json(status, json)))
+    pub fn list_versions(&mut self, tag: &str) -> Result<(), CliError> {
+        let repo = try!(Repo::from_str(tag));
+        let mut versions = Vec::new();
+        let mut seen = BTreeSet::new();
+        let mut offset = 0;
+        loop {
+            let task = ListVersions { repo: repo, offset: offset };
+            match try!(task.fetch(self)) {
+                Ok(page) => {
+                    let last = (page.len() as i64) < VERSIONS_PAGE_SIZE;
+                    offset += page.len() as i64;
+                    let listed = versions.len();
+                    versions.extend(page.into_iter().filter(|version| {
+                        seen.insert(version.version.clone())
+                    }));
+                    // A page with nothing new means the server isn't
+                    // paging, e.g. because it ignores the offset.
+                    if last || versions.len() == listed {
+                        break;
+                    }
+                },
+                Err(errors) => return report_errors(self, errors)
+            }
+        }
+        versions.sort_by(|a, b| compare_versions(&a.version[..], &b.version[..]));
+        let task = ListVersions { repo: repo, offset: 0 };
+        task.handle_result(self, Ok(versions))
     }
-}
 
-struct Check<'a> {
-    path: &'a str
-}
+    pub fn show_version(&mut self, tag: &str) -> Result<(), CliError> {
+        let task = ShowVersion { revision: try!(Revision::from_str(tag)) };
+        task.run(self)
+    }
 
-impl<'a> Task for Check<'a> {
-    type Result = StdResult<models::Validation, models::Validation>;
+    pub fn delete_version(&mut self, tag: &str, confirmed: bool) -> Result<(), CliError> {
+        let revision = try!(Revision::from_str(tag));
+        // Deleting `latest` would otherwise delete whatever version is
+        // latest by the time the request is made, not the one confirmed.
+        let version = try!(self.resolve_version(revision));
+        let Revision(repo, _) = revision;
+        let Repo(org, app) = repo;
+        let question = format!("delete {}/{}:{}?", org, app, version);
+        if !confirmed && !try!(self.confirm(&question[..])) {
+            return Err(CliError { desc: "aborted".to_string() });
+        }
+        let task = DeleteVersion { revision: Revision(repo, &version[..]) };
+        task.run(self)
+    }
 
-    fn perform_request(&self, cli: &mut Cli) -> CliResult<Response> {
-        let mut file = cli_try!(
-            File::open(self.path),
-            "failed to open input at `{}`: {}",
-            self.path);
-        let mut input = String::new();
-        cli_try!(
-            file.read_to_string(&mut input),
-            "failed reading from file at `{}`: {}",
-            self.path);
-        let validations = cli.validations();
-        Ok(cli_try!(validations.post(&input[..])))
+    pub fn pull(&mut self, tag: &str, path: &str, confirmed: bool) -> Result<(), CliError> {
+        let task = Pull {
+            revision: try!(Revision::from_str(tag)),
+            path: Path::new(path),
+            confirmed: confirmed
+        };
+        task.run(self)
     }
 
-    fn parse_json(&self, status: hyper::status::StatusCode, json: Json) -> CliResult<<Check as Task>::Result> {
-        let mut decoder = json::Decoder::new(json);
-        let result = models::Validation::decode(&mut decoder);
-        Ok(cli_try!(match status {
-            hyper::Ok => result.map(|v| Ok(v)),
-            _ => result.map(|v| Err(v))
-        }))
+    pub fn list_generators(&mut self) -> Result<(), CliError> {
+        ListGenerators.run(self)
     }
 
-    fn handle_result(&self, cli: &mut Cli, result: <Check as Task>::Result) -> CliResult<()> {
-        match result {
-            Ok(_) => Ok(()),
-            Err(validation) => {
-                for err in validation.errors {
-                    err!(cli, "validation error: {}", err);
-                }
-                Err(CliError { desc: "input invalid".to_string() })
-            }
+    pub fn show_generator(&mut self, key: &str) -> Result<(), CliError> {
+        let task = ShowGenerator { key: key };
+        task.run(self)
+    }
+
+    pub fn create_generator(&mut self, form: models::GeneratorCreateForm) -> Result<(), CliError> {
+        cli_try!(form.visibility.valid(), "invalid visiblity: {}");
+        let task = CreateGenerator { form: form };
+        task.run(self)
+    }
+
+    pub fn update_generator(
+        &mut self,
+        key: &str,
+        visibility: Option<models::Visibility>,
+        enabled: Option<&str>
+    ) -> Result<(), CliError> {
+        if let Some(ref visibility) = visibility {
+            cli_try!(visibility.valid(), "invalid visiblity: {}");
         }
+        let enabled = match enabled {
+            Some(enabled) => Some(cli_try!(
+                enabled.parse::<bool>(),
+                "invalid value for --enabled `{}`: {}",
+                enabled)),
+            None => None
+        };
+        let task = UpdateGenerator {
+            key: key,
+            form: models::GeneratorUpdateForm {
+                visibility: visibility,
+                enabled: enabled
+            }
+        };
+        task.run(self)
     }
-}
 
-struct Generate<'a> {
-    target: GenerateTarget<'a>
-}
+    pub fn enable_generator(&mut self, key: &str, org: &str, enabled: bool) -> Result<(), CliError> {
+        let task = EnableGenerator { key: key, org: org, enabled: enabled };
+        task.run(self)
+    }
 
-impl<'a> Task for Generate<'a> {
-    type Result = StdResult<models::Code, Vec<models::Error>>;
+    pub fn list_memberships(&mut self, org: &str) -> Result<(), CliError> {
+        let task = ListMemberships { org: org };
+        task.run(self)
+    }
 
-    fn perform_request(&self, cli: &mut Cli) -> CliResult<Response> {
-        let GenerateTarget(Revision(Repo(org, app), version), target) = self.target;
-        let client = cli.code();
-        err!(cli, "getting code for {}/{}:{}/{}", org, app, version, target);
-        Ok(cli_try!(
-            client.get_by_organization_key_and_application_key_and_version_and_generator_key(
-                org, app, version, target)))
+    pub fn list_membership_requests(&mut self, org: &str) -> Result<(), CliError> {
+        let task = ListMembershipRequests { org: org };
+        task.run(self)
     }
 
-    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<Generate as Task>::Result> {
-        let mut decoder = json::Decoder::new(json);
-        Ok(cli_try!(match status {
-            hyper::Ok => {
-                let result = models::Code::decode(&mut decoder);
-                result.map(|c| Ok(c))
-            },
-            _ => {
-                let result = Vec::<models::Error>::decode(&mut decoder);
-                result.map(|e| Err(e))
-            }
-        }))
+    pub fn review_membership_request(&mut self, guid: &str, accept: bool) -> Result<(), CliError> {
+        let task = ReviewMembershipRequest { guid: guid, accept: accept };
+        task.run(self)
     }
 
-    fn handle_result(&self, cli: &mut Cli, result: <Generate as Task>::Result) -> CliResult<()> {
-        match result {
-            Ok(code) => Ok(out!(cli, "{}", code.source)),
-            Err(errors) => {
-                for error in errors {
-                    err!(cli, "error: {}", error.message);
-                }
-                Err(CliError { desc: "got error response from server".to_string() })
+    pub fn create_membership_request(&mut self, org: &str, role: &str) -> Result<(), CliError> {
+        let task = ShowOrganization { key: org };
+        let organization = match try!(task.fetch(self)) {
+            Ok(organization) => organization,
+            Err(errors) => return report_errors(self, errors)
+        };
+        let user = try!(self.current_user());
+        let task = CreateMembershipRequest {
+            organization: organization,
+            user: user,
+            role: role
+        };
+        task.run(self)
+    }
+
+    pub fn list_tokens(&mut self) -> Result<(), CliError> {
+        let user = try!(self.current_user());
+        let task = ListTokens { user: user };
+        task.run(self)
+    }
+
+    pub fn create_token(&mut self, description: Option<String>) -> Result<(), CliError> {
+        let user = try!(self.current_user());
+        let task = CreateToken {
+            form: models::TokenForm {
+                user_guid: user.guid,
+                description: description
             }
-        }
+        };
+        let token = match try!(task.fetch(self)) {
+            Ok(token) => token,
+            Err(errors) => return report_errors(self, errors)
+        };
+        let task = ShowCleartextToken { guid: token.guid.to_string() };
+        task.run(self)
     }
-}
 
-struct Push<'a> {
-    tag: &'a str,
-    path: &'a str,
-    visibility: &'a models::Visibility
-}
+    pub fn revoke_token(&mut self, guid: &str) -> Result<(), CliError> {
+        let task = RevokeToken { guid: guid };
+        task.run(self)
+    }
 
-impl<'a> Task for Push<'a> {
-    type Result = StdResult<models::Version, Vec<models::Error>>;
+    pub fn list_subscriptions(&mut self) -> Result<(), CliError> {
+        let user = try!(self.current_user());
+        let task = ListSubscriptions { user: user };
+        task.run(self)
+    }
 
-    fn perform_request(&self, cli: &mut Cli) -> CliResult<Response> {
-        cli_try!(self.visibility.valid(), "invalid visiblity: {}");
-        let Revision(Repo(org, app), version) = try!(Revision::from_str(self.tag));
-        let mut file = cli_try!(
-            File::open(self.path), "failed to open {}: {}", self.path);
-        let mut input = String::new();
-        cli_try!(file.read_to_string(&mut input));
-        let form = models::VersionForm {
-            visibility: Some(self.visibility.clone()),
-            original_form: models::OriginalForm {
-                original_type: None,
-                data: input
+    pub fn subscribe(&mut self, org: &str, publication: models::Publication) -> Result<(), CliError> {
+        cli_try!(
+            publication.valid(),
+            "invalid publication `{}`, expected one of membership_requests.create, \
+             memberships.create, applications.create or versions.create");
+        let user = try!(self.current_user());
+        let task = Subscribe {
+            form: models::SubscriptionForm {
+                organization_key: org.to_string(),
+                user_guid: user.guid,
+                publication: publication
             }
         };
-        out!(cli, "pushing to {}/{}:{}", org, app, version);
-        Ok(cli_try!(cli.versions()
-            .put_by_organization_key_and_application_key_and_version(
-                org, app, version, form)))
+        task.run(self)
     }
 
-    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<Push as Task>::Result> {
-        let mut decoder = json::Decoder::new(json);
-        Ok(cli_try!(match status {
-            hyper::Ok => models::Version::decode(&mut decoder).map(|v| Ok(v)),
-            _ => Vec::<models::Error>::decode(&mut decoder).map(|e| Err(e))
-        }))
+    pub fn unsubscribe(&mut self, guid: &str) -> Result<(), CliError> {
+        let task = Unsubscribe { guid: guid };
+        task.run(self)
     }
 
-    fn handle_result(&self, cli: &mut Cli, result: <Push as Task>::Result) -> CliResult<()> {
-        match result {
-            Ok(_) => Ok(()),
-            Err(errors) => {
-                for error in errors {
-                    err!(cli, "error: {}", error.message);
-                }
-                Err(CliError { desc: "got error response from server".to_string() })
+    pub fn list_watches(&mut self) -> Result<(), CliError> {
+        let user = try!(self.current_user());
+        let task = ListWatches { user: user, repo: None };
+        task.run(self)
+    }
+
+    pub fn watch(&mut self, tag: &str) -> Result<(), CliError> {
+        let Repo(org, app) = try!(Repo::from_str(tag));
+        let user = try!(self.current_user());
+        let task = Watch {
+            form: models::WatchForm {
+                user_guid: user.guid,
+                organization_key: org.to_string(),
+                application_key: app.to_string()
             }
+        };
+        task.run(self)
+    }
+
+    pub fn unwatch(&mut self, tag: &str) -> Result<(), CliError> {
+        let repo = try!(Repo::from_str(tag));
+        let user = try!(self.current_user());
+        let task = ListWatches { user: user, repo: Some(repo) };
+        let watches = match try!(task.fetch(self)) {
+            Ok(watches) => watches,
+            Err(errors) => return report_errors(self, errors)
+        };
+        if watches.is_empty() {
+            return Err(CliError { desc: format!("not watching {}", tag) });
         }
+        for watch in watches {
+            let guid = watch.guid.to_string();
+            let task = Unwatch { guid: &guid[..], tag: tag };
+            try!(task.run(self));
+        }
+        Ok(())
+    }
+
+    pub fn create_user(
+        &mut self,
+        email: &str,
+        nickname: Option<String>,
+        name: Option<String>
+    ) -> Result<(), CliError> {
+        let password = try!(self.prompt_new_password("password:"));
+        let task = CreateUser {
+            form: models::UserForm {
+                email: email.to_string(),
+                password: password,
+                nickname: nickname,
+                name: name
+            }
+        };
+        task.run(self)
+    }
+
+    pub fn update_user(
+        &mut self,
+        email: Option<String>,
+        nickname: Option<String>,
+        name: Option<String>
+    ) -> Result<(), CliError> {
+        let user = try!(self.current_user());
+        let task = UpdateUser {
+            guid: user.guid.to_string(),
+            form: models::UserUpdateForm {
+                email: email.unwrap_or(user.email),
+                nickname: nickname.unwrap_or(user.nickname),
+                name: name.or(user.name)
+            }
+        };
+        task.run(self)
+    }
+
+    pub fn request_password_reset(&mut self, email: &str) -> Result<(), CliError> {
+        let task = RequestPasswordReset { email: email };
+        task.run(self)
+    }
+
+    pub fn reset_password(&mut self, token: &str) -> Result<(), CliError> {
+        let password = try!(self.prompt_new_password("new password:"));
+        let task = ResetPassword { token: token, password: &password[..] };
+        task.run(self)
+    }
+
+    pub fn confirm_email(&mut self, token: &str) -> Result<(), CliError> {
+        let task = ConfirmEmail { token: token };
+        task.run(self)
+    }
+
+    pub fn whoami(&mut self, profile_name: &str) -> Result<(), CliError> {
+        let user = try!(self.current_user());
+        let api_url = self.api_url();
+        out!(self, "profile: {}", profile_name);
+        out!(self, "api_url: {}", api_url);
+        out!(self, "guid: {}", user.guid);
+        out!(self, "email: {}", user.email);
+        out!(self, "nickname: {}", user.nickname);
+        if let Some(ref name) = user.name {
+            out!(self, "name: {}", name);
+        }
+        let task = ListUserMemberships { user: user };
+        task.run(self)
+    }
+
+    // Looks up the user that the configured token belongs to.
+    fn current_user(&mut self) -> CliResult<models::User> {
+        let task = CurrentUser { token: self.config.token.clone() };
+        match try!(task.fetch(self)) {
+            Ok(users) => Ok(cli_opt!(
+                users.into_iter().next(),
+                "no user found for the configured token")),
+            Err(errors) => report_errors(self, errors)
+        }
+    }
+
+    pub fn add_domain(&mut self, org: &str, domain: &str) -> Result<(), CliError> {
+        let task = AddDomain { org: org, domain: domain };
+        task.run(self)
+    }
+
+    pub fn remove_domain(&mut self, org: &str, domain: &str) -> Result<(), CliError> {
+        let task = RemoveDomain { org: org, domain: domain };
+        task.run(self)
+    }
+
+    pub fn list_organizations(&mut self) -> Result<(), CliError> {
+        ListOrganizations.run(self)
+    }
+
+    pub fn show_organization(&mut self, key: &str) -> Result<(), CliError> {
+        let task = ShowOrganization { key: key };
+        task.run(self)
+    }
+
+    pub fn create_organization(&mut self, form: models::OrganizationForm) -> Result<(), CliError> {
+        if let Some(ref visibility) = form.visibility {
+            cli_try!(visibility.valid(), "invalid visiblity: {}");
+        }
+        let task = CreateOrganization { form: form };
+        task.run(self)
+    }
+
+    pub fn delete_organization(&mut self, key: &str, confirmed: bool) -> Result<(), CliError> {
+        if !confirmed && !try!(self.confirm(&format!("delete organization {}?", key)[..])) {
+            return Err(CliError { desc: "aborted".to_string() });
+        }
+        let task = DeleteOrganization { key: key };
+        task.run(self)
+    }
+
+    // Asks the user a yes or no question, defaulting to no.
+    fn confirm(&mut self, question: &str) -> CliResult<bool> {
+        let answer = try!(self.prompt(&format!("{} [y/N]", question)[..]));
+        Ok(answer == "y" || answer == "yes")
+    }
+
+    fn prompt(&mut self, question: &str) -> CliResult<String> {
+        cli_try!(
+            write!(self.err, "{} ", question),
+            "failed writing to error stream: {}");
+        cli_try!(self.err.flush(), "failed writing to error stream: {}");
+        let mut answer = String::new();
+        cli_try!(
+            stdin().read_line(&mut answer),
+            "failed reading from input stream: {}");
+        Ok(answer.trim().to_string())
+    }
+
+    // Like `prompt`, but turns off echoing of the input
+    // while it is being typed, if the terminal allows it.
+    fn prompt_password(&mut self, question: &str) -> CliResult<String> {
+        // Input that isn't typed, e.g. piped in, isn't echoed anyway.
+        if !stdin_is_tty() {
+            return self.prompt(question);
+        }
+        let answer = {
+            let _no_echo = NoEcho::new();
+            self.prompt(question)
+        };
+        err!(self, "{}", "");
+        answer
+    }
+
+    // Like `prompt_password`, for a password that is being chosen: it is
+    // asked for twice, so that a typo in it doesn't go unnoticed.
+    fn prompt_new_password(&mut self, question: &str) -> CliResult<String> {
+        let password = try!(self.prompt_password(question));
+        let repeated = try!(self.prompt_password(&format!("repeat {}", question)[..]));
+        if password != repeated {
+            return Err(CliError { desc: "passwords do not match".to_string() });
+        }
+        Ok(password)
+    }
+
+    fn api_url(&self) -> String {
+        self.config.api_url.clone().unwrap_or(
+            "http://api.apidoc.me".to_string())
+    }
+
+    fn applications(&self) -> client::Applications {
+        client::Applications::new(self.api_url(), self.config.token.clone())
+    }
+
+    fn code(&self) -> client::Code {
+        client::Code::new(self.api_url(), self.config.token.clone())
+    }
+
+    fn domains(&self) -> client::Domains {
+        client::Domains::new(self.api_url(), self.config.token.clone())
+    }
+
+    fn email_verification_confirmations(&self) -> client::EmailVerificationConfirmationForms {
+        client::EmailVerificationConfirmationForms::new(self.api_url(), self.config.token.clone())
+    }
+
+    fn generators(&self) -> client::Generators {
+        client::Generators::new(self.api_url(), self.config.token.clone())
+    }
+
+    fn healthchecks(&self) -> client::Healthchecks {
+        client::Healthchecks::new(self.api_url())
+    }
+
+    fn membership_requests(&self) -> client::MembershipRequests {
+        client::MembershipRequests::new(self.api_url(), self.config.token.clone())
+    }
+
+    fn memberships(&self) -> client::Memberships {
+        client::Memberships::new(self.api_url(), self.config.token.clone())
+    }
+
+    fn organizations(&self) -> client::Organizations {
+        client::Organizations::new(self.api_url(), self.config.token.clone())
+    }
+
+    fn password_reset_requests(&self) -> client::PasswordResetRequests {
+        client::PasswordResetRequests::new(self.api_url(), self.config.token.clone())
+    }
+
+    fn password_resets(&self) -> client::PasswordResets {
+        client::PasswordResets::new(self.api_url(), self.config.token.clone())
+    }
+
+    fn subscriptions(&self) -> client::Subscriptions {
+        client::Subscriptions::new(self.api_url(), self.config.token.clone())
+    }
+
+    fn tokens(&self) -> client::Tokens {
+        client::Tokens::new(self.api_url(), self.config.token.clone())
+    }
+
+    fn users(&self) -> client::Users {
+        client::Users::new(self.api_url(), self.config.token.clone())
+    }
+
+    fn watches(&self) -> client::Watches {
+        client::Watches::new(self.api_url(), self.config.token.clone())
+    }
+
+
+    fn validations(&self) -> client::Validations {
+        client::Validations::new(self.api_url())
+    }
+
+    fn versions(&self) -> client::Versions {
+        client::Versions::new(self.api_url(), self.config.token.clone())
+    }
+}
+
+#[cfg(unix)]
+fn stdin_is_tty() -> bool {
+    extern {
+        fn isatty(fd: i32) -> i32;
+    }
+    unsafe { isatty(0) == 1 }
+}
+
+#[cfg(not(unix))]
+fn stdin_is_tty() -> bool {
+    false
+}
+
+// Turns off echoing of input on the terminal until it is dropped, so
+// that echoing is turned back on whether prompting for input succeeds
+// or fails. An interrupt (Ctrl-C) ends the process without dropping
+// it, though, leaving echoing off until e.g. `stty echo` is run.
+struct NoEcho;
+
+impl NoEcho {
+    fn new() -> Option<NoEcho> {
+        match Command::new("stty").arg("-echo").status() {
+            Ok(ref status) if status.success() => Some(NoEcho),
+            _ => None
+        }
+    }
+}
+
+impl Drop for NoEcho {
+    fn drop(&mut self) {
+        let _ = Command::new("stty").arg("echo").status();
+    }
+}
+
+trait Task {
+    type Result;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>>;
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<Self::Result>;
+
+    fn handle_result(&self, cli: &mut Cli, result: Self::Result) -> CliResult<()>;
+
+    fn fetch(&self, cli: &mut Cli) -> CliResult<Self::Result> {
+        let mut res = match try!(self.perform_request(cli)) {
+            Ok(res) => res,
+            // Only failing to talk to the server at all means it is
+            // unreachable; anything else is a problem with the request.
+            Err(HttpError::Io(err)) => return Err(CliError {
+                desc: format!("server unreachable at {}: {}", cli.api_url(), err)
+            }),
+            Err(err) => return Err(CliError::from_err(&err))
+        };
+        let status = res.status;
+        let mut body = String::new();
+        cli_try!(
+            res.read_to_string(&mut body),
+            "failed reading HTTP response body (status was {}): {}",
+            status);
+        // Some responses, e.g. to a DELETE, have no body at all.
+        let json = if body.trim().is_empty() {
+            Json::Null
+        } else {
+            match Json::from_str(&body[..]) {
+                Ok(json) => json,
+                // Errors from the service itself come as JSON, so anything
+                // else, like an HTML error page, is from whatever is in
+                // front of it.
+                Err(_) if status.is_server_error() => return Err(CliError {
+                    desc: format!(
+                        "server at {} is not healthy: responded with {}",
+                        cli.api_url(), status)
+                }),
+                Err(_) if !status.is_success() => return Err(CliError {
+                    desc: format!("server at {} responded with {}", cli.api_url(), status)
+                }),
+                Err(err) => return Err(CliError {
+                    desc: format!(
+                        "failed to parse HTTP response body as JSON (status was {}): {}",
+                        status, err)
+                })
+            }
+        };
+        self.parse_json(status, json)
+    }
+
+    fn run(&self, cli: &mut Cli) -> CliResult<()> {
+        let result = try!(self.fetch(cli));
+        self.handle_result(cli, result)
+    }
+}
+
+fn read_file(path: &str) -> CliResult<String> {
+    let mut file = cli_try!(
+        File::open(path),
+        "failed to open input at `{}`: {}",
+        path);
+    let mut input = String::new();
+    cli_try!(
+        file.read_to_string(&mut input),
+        "failed reading from file at `{}`: {}",
+        path);
+    Ok(input)
+}
+
+// Guesses the type of an input from its extension, falling back to its
+// content: JSON with a top-level `swagger` key is Swagger, any other JSON
+// object is api.json and anything starting with a protocol declaration
+// is Avro IDL. Anything else is left for the user to give with `--type`.
+fn detect_original_type(path: &str, input: &str) -> Option<models::OriginalType> {
+    let extension = Path::new(path).extension().and_then(|ext| ext.to_str());
+    if extension == Some("avdl") {
+        return Some(models::OriginalType::AvroIdl);
+    }
+    match Json::from_str(input) {
+        Ok(Json::Object(ref obj)) if obj.contains_key("swagger") => {
+            Some(models::OriginalType::SwaggerJson)
+        },
+        Ok(Json::Object(_)) => Some(models::OriginalType::ApiJson),
+        Ok(_) => None,
+        Err(_) if declares_protocol(input) => Some(models::OriginalType::AvroIdl),
+        Err(_) => None
+    }
+}
+
+// Whether `input` starts with `protocol <Name> {`, ignoring comments and
+// annotations such as `@namespace("com.example")` before it.
+fn declares_protocol(input: &str) -> bool {
+    let mut rest = input;
+    loop {
+        rest = rest.trim_left();
+        if rest.starts_with("//") {
+            rest = match rest.find('\n') {
+                Some(end) => &rest[end..],
+                None => ""
+            };
+        } else if rest.starts_with("/*") {
+            rest = match rest[2..].find("*/") {
+                Some(end) => &rest[2 + end + 2..],
+                None => return false
+            };
+        } else if rest.starts_with("@") {
+            // Annotation names may contain dashes, as in `@java-class`.
+            let end = rest[1..]
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '-' || c == '.'))
+                .unwrap_or(rest.len() - 1);
+            if end == 0 {
+                return false;
+            }
+            rest = rest[1 + end..].trim_left();
+            if rest.starts_with("(") {
+                rest = match skip_parens(rest) {
+                    Some(after) => after,
+                    None => return false
+                };
+            }
+        } else {
+            break;
+        }
+    }
+    if identifier(rest) != "protocol" {
+        return false;
+    }
+    rest = rest["protocol".len()..].trim_left();
+    let name = identifier(rest);
+    !name.is_empty() && rest[name.len()..].trim_left().starts_with("{")
+}
+
+// The identifier at the start of `input`, which may be empty.
+fn identifier(input: &str) -> &str {
+    let end = input
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(input.len());
+    &input[..end]
+}
+
+// Skips the parenthesised annotation arguments at the start of `input`,
+// which may contain strings, returning what follows them.
+fn skip_parens(input: &str) -> Option<&str> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in input.char_indices() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&input[i + 1..]);
+                }
+            },
+            _ => ()
+        }
+    }
+    None
+}
+
+struct Check<'a> {
+    path: &'a str,
+    input: String,
+    format: report::Format,
+    // Problems found locally, reported along with the server's result.
+    warnings: Vec<String>
+}
+
+impl<'a> Task for Check<'a> {
+    type Result = StdResult<models::Validation, models::Validation>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let validations = cli.validations();
+        Ok(validations.post(&self.input[..]))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<Check as Task>::Result> {
+        let mut decoder = json::Decoder::new(json);
+        let result = models::Validation::decode(&mut decoder);
+        Ok(cli_try!(match status {
+            hyper::Ok => result.map(|v| Ok(v)),
+            _ => result.map(|v| Err(v))
+        }))
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <Check as Task>::Result) -> CliResult<()> {
+        let errors = match result {
+            Ok(_) => Vec::new(),
+            Err(validation) => validation.errors
+        };
+        let valid = errors.is_empty();
+        let document = locate::parse(&self.input[..]);
+        let warnings: Vec<(report::Level, String)> = self.warnings.iter()
+            .filter(|warning| !errors.contains(warning))
+            .map(|warning| (report::Level::Warning, warning.clone()))
+            .collect();
+        let errors = errors.into_iter().map(|err| (report::Level::Error, err));
+        let diagnostics: Vec<report::Diagnostic> = errors.chain(warnings.into_iter()).map(|(level, message)| {
+            let position = document.as_ref().and_then(|root| locate::find(root, &message[..]));
+            report::Diagnostic { level: level, message: message, position: position }
+        }).collect();
+        try!(self.report(cli, &diagnostics[..]));
+        if valid {
+            Ok(())
+        } else {
+            Err(CliError { desc: "input invalid".to_string() })
+        }
+    }
+}
+
+impl<'a> Check<'a> {
+    fn report(&self, cli: &mut Cli, diagnostics: &[report::Diagnostic]) -> CliResult<()> {
+        match self.format {
+            report::Format::Human => {
+                for diagnostic in diagnostics.iter() {
+                    match diagnostic.position {
+                        Some(position) => {
+                            err!(cli, "{}:{}:{}: validation {}: {}",
+                                 self.path, position.line, position.col,
+                                 diagnostic.level, diagnostic.message);
+                            if let Some(excerpt) = locate::excerpt(&self.input[..], position) {
+                                err!(cli, "{}", excerpt);
+                            }
+                        },
+                        None => err!(cli, "validation {}: {}", diagnostic.level, diagnostic.message)
+                    }
+                }
+            },
+            report::Format::Json => out!(cli, "{}", report::json(self.path, diagnostics)),
+            report::Format::Junit => out!(cli, "{}", report::junit(self.path, diagnostics)),
+            report::Format::Sarif => out!(cli, "{}", report::sarif(self.path, diagnostics))
+        }
+        Ok(())
+    }
+
+    // Reports an error that kept the input from being validated at all,
+    // e.g. an unreachable server. Tools reading one of the machine
+    // readable formats still get a document, with the error in it.
+    fn fail(&self, cli: &mut Cli, err: CliError) -> CliResult<()> {
+        if self.format != report::Format::Human {
+            let diagnostic = report::Diagnostic {
+                level: report::Level::Error,
+                message: err.desc.clone(),
+                position: None
+            };
+            try!(self.report(cli, &[diagnostic]));
+        }
+        Err(err)
+    }
+}
+
+fn report_errors<T>(cli: &mut Cli, errors: Vec<models::Error>) -> CliResult<T> {
+    for error in errors {
+        err!(cli, "error: {}", error.message);
+    }
+    Err(CliError { desc: "got error response from server".to_string() })
+}
+
+fn decode_response<T: Decodable>(
+    status: StatusCode,
+    json: Json
+) -> CliResult<StdResult<T, Vec<models::Error>>> {
+    if !status.is_success() && json.is_null() {
+        return Ok(Err(vec![models::Error {
+            code: status.to_string(),
+            message: format!("server responded with {}", status)
+        }]));
+    }
+    let mut decoder = json::Decoder::new(json);
+    Ok(cli_try!(if status.is_success() {
+        T::decode(&mut decoder).map(|t| Ok(t))
+    } else {
+        Vec::<models::Error>::decode(&mut decoder).map(|e| Err(e))
+    }))
+}
+
+// Drops the resolved service from a version, or a list of versions, for
+// requests that don't use it, so that changes to the spec can't break them.
+fn without_service(json: Json) -> Json {
+    match json {
+        Json::Object(mut obj) => {
+            obj.remove("service");
+            Json::Object(obj)
+        },
+        Json::Array(versions) => Json::Array(versions.into_iter().map(without_service).collect()),
+        json => json
+    }
+}
+
+// Like `decode_response`, for requests where the body
+// of a successful response is of no interest.
+fn decode_status(
+    status: StatusCode,
+    json: Json
+) -> CliResult<StdResult<(), Vec<models::Error>>> {
+    if status.is_success() {
+        Ok(Ok(()))
+    } else {
+        decode_response(status, json)
+    }
+}
+
+struct Healthcheck;
+
+impl Task for Healthcheck {
+    type Result = StdResult<models::Healthcheck, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        Ok(cli.healthchecks().get_internal_and_healthcheck())
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<Healthcheck as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <Healthcheck as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(healthcheck) => Ok(out!(cli, "{}", healthcheck.status)),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct Generate<'a> {
+    target: GenerateTarget<'a>,
+    output: Option<&'a Path>,
+    check: bool
+}
+
+impl<'a> Task for Generate<'a> {
+    type Result = StdResult<models::Code, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let GenerateTarget(Revision(Repo(org, app), version), target) = self.target;
+        let client = cli.code();
+        err!(cli, "getting code for {}/{}:{}/{}", org, app, version, target);
+        Ok(client.get_by_organization_key_and_application_key_and_version_and_generator_key(
+            org, app, version, target))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<Generate as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <Generate as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(code) => match self.output {
+                Some(path) if self.check => check_file(cli, path, &code.source[..]),
+                Some(path) => {
+                    try!(write_file(path, &code.source[..]));
+                    Ok(err!(cli, "wrote {}", path.display()))
+                },
+                None => Ok(out!(cli, "{}", code.source))
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+// Compares `contents` with the file at `path`, printing
+// a unified diff and failing if they differ.
+fn check_file(cli: &mut Cli, path: &Path, contents: &str) -> CliResult<()> {
+    let existing = try!(read_existing(path)).unwrap_or(Vec::new());
+    // Whether the file is up to date is decided on the bytes; the diff
+    // is only there to show how it differs.
+    if &existing[..] == contents.as_bytes() {
+        return Ok(());
+    }
+    let name = path.display().to_string();
+    let existing = String::from_utf8_lossy(&existing[..]);
+    match diff::unified(&existing[..], contents, &name[..], &name[..]) {
+        Some(diff) => out!(cli, "{}", diff.trim_right_matches('\n')),
+        None => out!(cli, "`{}` differs only in line endings or trailing newlines", name)
+    }
+    Err(CliError { desc: format!("generated code differs from `{}`", name) })
+}
+
+// Reads the file at `path`, if there is one.
+fn read_existing(path: &Path) -> CliResult<Option<Vec<u8>>> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(CliError {
+            desc: format!("failed to open `{}`: {}", path.display(), err)
+        })
+    };
+    let mut contents = Vec::new();
+    cli_try!(
+        file.read_to_end(&mut contents),
+        "failed reading from `{}`: {}",
+        path.display());
+    Ok(Some(contents))
+}
+
+// Writes `contents` to `path`, creating any missing directories.
+// The contents are written to a temporary file next to `path` first
+// and then renamed over it, so that a failure part way through never
+// leaves a truncated file behind.
+fn write_file(path: &Path, contents: &str) -> CliResult<()> {
+    replace_file(path, contents, false)
+}
+
+// Like `write_file`, but only the current user may read the file.
+fn write_private_file(path: &Path, contents: &str) -> CliResult<()> {
+    replace_file(path, contents, true)
+}
+
+fn replace_file(path: &Path, contents: &str, private: bool) -> CliResult<()> {
+    if let Some(dir) = path.parent() {
+        if !dir.as_os_str().is_empty() {
+            cli_try!(
+                fs::create_dir_all(dir),
+                "failed to create directory `{}`: {}",
+                dir.display());
+        }
+    }
+    let file_name = cli_opt!(path.file_name(), "invalid output path: {}", path.display());
+    let tmp_path = path.with_file_name(
+        format!(".{}.tmp", file_name.to_string_lossy()));
+    let written = File::create(&tmp_path).and_then(|mut file| {
+        if private {
+            try!(restrict_permissions(&tmp_path));
+        }
+        try!(file.write_all(contents.as_bytes()));
+        file.sync_all()
+    });
+    if let Err(err) = written {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(CliError {
+            desc: format!("failed writing to `{}`: {}", tmp_path.display(), err)
+        });
+    }
+    Ok(cli_try!(
+        fs::rename(&tmp_path, path),
+        "failed to move `{}` to `{}`: {}",
+        tmp_path.display(), path.display()))
+}
+
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+struct Push<'a> {
+    revision: Revision<'a>,
+    input: String,
+    original_type: Option<models::OriginalType>,
+    visibility: &'a models::Visibility
+}
+
+impl<'a> Task for Push<'a> {
+    type Result = StdResult<models::Version, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let Revision(Repo(org, app), version) = self.revision;
+        let form = models::VersionForm {
+            visibility: Some(self.visibility.clone()),
+            original_form: models::OriginalForm {
+                original_type: self.original_type.clone(),
+                data: self.input.clone()
+            }
+        };
+        out!(cli, "pushing to {}/{}:{}", org, app, version);
+        Ok(cli.versions()
+            .put_by_organization_key_and_application_key_and_version(
+                org, app, version, form))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<Push as Task>::Result> {
+        decode_response(status, without_service(json))
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <Push as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(_) => Ok(()),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+// Number of versions requested per page when listing versions.
+const VERSIONS_PAGE_SIZE: i64 = 100;
+
+struct ListVersions<'a> {
+    repo: Repo<'a>,
+    offset: i64
+}
+
+impl<'a> Task for ListVersions<'a> {
+    type Result = StdResult<Vec<models::Version>, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let Repo(org, app) = self.repo;
+        Ok(cli.versions()
+            .get_by_organization_key_and_application_key(
+                org, app, Some(VERSIONS_PAGE_SIZE), Some(self.offset)))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ListVersions as Task>::Result> {
+        decode_response(status, without_service(json))
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ListVersions as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(versions) => {
+                for version in versions {
+                    out!(cli, "{}", version.version);
+                }
+                Ok(())
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+// Orders versions the way people read them, so that 0.9.10 comes
+// after 0.9.9 and 1.0.0-dev comes before 1.0.0. Parts that are not
+// numeric are compared lexically.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    fn split(version: &str) -> (&str, Option<&str>) {
+        match version.find('-') {
+            Some(idx) => (&version[..idx], Some(&version[idx + 1..])),
+            None => (version, None)
+        }
+    }
+
+    fn compare_parts(a: &str, b: &str) -> Ordering {
+        let mut a_parts = a.split('.');
+        let mut b_parts = b.split('.');
+        loop {
+            let ord = match (a_parts.next(), b_parts.next()) {
+                (None, None) => return Ordering::Equal,
+                (None, Some(_)) => return Ordering::Less,
+                (Some(_), None) => return Ordering::Greater,
+                (Some(x), Some(y)) => match (x.parse::<u64>(), y.parse::<u64>()) {
+                    (Ok(x), Ok(y)) => x.cmp(&y),
+                    (Ok(_), Err(_)) => Ordering::Less,
+                    (Err(_), Ok(_)) => Ordering::Greater,
+                    (Err(_), Err(_)) => x.cmp(y)
+                }
+            };
+            if ord != Ordering::Equal {
+                return ord;
+            }
+        }
+    }
+
+    let (a_core, a_pre) = split(a);
+    let (b_core, b_pre) = split(b);
+    match compare_parts(a_core, b_core) {
+        Ordering::Equal => match (a_pre, b_pre) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(x), Some(y)) => compare_parts(x, y)
+        },
+        ord => ord
+    }
+}
+
+struct ShowVersion<'a> {
+    revision: Revision<'a>
+}
+
+impl<'a> Task for ShowVersion<'a> {
+    type Result = StdResult<models::Version, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let Revision(Repo(org, app), version) = self.revision;
+        Ok(cli.versions()
+            .get_by_organization_key_and_application_key_and_version(
+                org, app, version))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ShowVersion as Task>::Result> {
+        let service = json.find("service").map(|service| service.clone());
+        let mut result = try!(decode_response(status, without_service(json)));
+        if let Ok(ref mut version) = result {
+            // A service that can't be decoded, e.g. because the spec has
+            // changed, shouldn't stop the rest of the version being shown.
+            if let Some(service) = service {
+                let mut decoder = json::Decoder::new(service);
+                version.service = spec::Service::decode(&mut decoder).ok();
+            }
+        }
+        Ok(result)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ShowVersion as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(version) => {
+                out!(cli, "guid: {}", version.guid);
+                out!(cli, "organization: {}", version.organization.key);
+                out!(cli, "application: {}", version.application.key);
+                out!(cli, "version: {}", version.version);
+                if let Some(ref original) = version.original {
+                    out!(cli, "original type: {}", original.original_type);
+                }
+                let service = match version.service {
+                    Some(ref service) => service,
+                    None => return Ok(())
+                };
+                out!(cli, "service: {}", service.name);
+                out!(cli, "namespace: {}", service.namespace);
+                if let Some(ref base_url) = service.base_url {
+                    out!(cli, "base url: {}", base_url);
+                }
+                for import in service.imports.iter() {
+                    out!(cli, "import: {}", import.uri);
+                }
+                for enumeration in service.enums.iter() {
+                    out!(cli, "enum: {}", enumeration.name);
+                }
+                for model in service.models.iter() {
+                    out!(cli, "model: {}", model.name);
+                }
+                for resource in service.resources.iter() {
+                    for operation in resource.operations.iter() {
+                        out!(cli, "operation: {} {}", operation.method, operation.path);
+                    }
+                }
+                Ok(())
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct DeleteVersion<'a> {
+    revision: Revision<'a>
+}
+
+impl<'a> Task for DeleteVersion<'a> {
+    type Result = StdResult<(), Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let Revision(Repo(org, app), version) = self.revision;
+        err!(cli, "deleting {}/{}:{}", org, app, version);
+        Ok(cli.versions()
+            .delete_by_organization_key_and_application_key_and_version(
+                org, app, version))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<DeleteVersion as Task>::Result> {
+        decode_status(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <DeleteVersion as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct Pull<'a> {
+    revision: Revision<'a>,
+    path: &'a Path,
+    // Whether an existing file that differs may be overwritten
+    // without asking first.
+    confirmed: bool
+}
+
+impl<'a> Task for Pull<'a> {
+    type Result = StdResult<models::Version, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let Revision(Repo(org, app), version) = self.revision;
+        err!(cli, "pulling {}/{}:{}", org, app, version);
+        Ok(cli.versions()
+            .get_by_organization_key_and_application_key_and_version(
+                org, app, version))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<Pull as Task>::Result> {
+        decode_response(status, without_service(json))
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <Pull as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(version) => {
+                let original = cli_opt!(
+                    version.original,
+                    "no original input was stored for {}/{}:{}",
+                    version.organization.key, version.application.key, version.version);
+                let existing = try!(read_existing(self.path));
+                let differs = existing.map_or(false, |existing| existing != original.data.as_bytes());
+                if differs && !self.confirmed {
+                    let question = format!("overwrite {}?", self.path.display());
+                    if !try!(cli.confirm(&question[..])) {
+                        return Err(CliError { desc: "aborted".to_string() });
+                    }
+                }
+                try!(write_file(self.path, &original.data[..]));
+                Ok(err!(cli, "wrote {} ({})", self.path.display(), original.original_type))
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct ListApplications<'a> {
+    org: &'a str
+}
+
+impl<'a> Task for ListApplications<'a> {
+    type Result = StdResult<Vec<models::Application>, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        Ok(cli.applications().get_by_organization_key(self.org, None))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ListApplications as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ListApplications as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(applications) => {
+                for app in applications {
+                    out!(cli, "{}\t{}\t{}", app.key, app.name, app.visibility);
+                }
+                Ok(())
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct ShowApplication<'a> {
+    repo: Repo<'a>
+}
+
+impl<'a> Task for ShowApplication<'a> {
+    type Result = StdResult<Vec<models::Application>, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let Repo(org, app) = self.repo;
+        Ok(cli.applications().get_by_organization_key(org, Some(app)))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ShowApplication as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ShowApplication as Task>::Result) -> CliResult<()> {
+        let Repo(org, app) = self.repo;
+        match result {
+            Ok(applications) => {
+                let application = cli_opt!(
+                    applications.into_iter().next(),
+                    "application not found: {}/{}", org, app);
+                print_application(cli, &application)
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+fn print_application(cli: &mut Cli, app: &models::Application) -> CliResult<()> {
+    out!(cli, "guid: {}", app.guid);
+    out!(cli, "organization: {}", app.organization.key);
+    out!(cli, "key: {}", app.key);
+    out!(cli, "name: {}", app.name);
+    out!(cli, "visibility: {}", app.visibility);
+    if let Some(ref description) = app.description {
+        out!(cli, "description: {}", description);
+    }
+    Ok(())
+}
+
+struct CreateApplication<'a> {
+    org: &'a str,
+    form: models::ApplicationForm
+}
+
+impl<'a> Task for CreateApplication<'a> {
+    type Result = StdResult<models::Application, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "creating application {} in {}", self.form.name, self.org);
+        Ok(cli.applications().post_by_organization_key(self.org, self.form.clone()))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<CreateApplication as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <CreateApplication as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(app) => print_application(cli, &app),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct DeleteApplication<'a> {
+    repo: Repo<'a>
+}
+
+impl<'a> Task for DeleteApplication<'a> {
+    type Result = StdResult<(), Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let Repo(org, app) = self.repo;
+        err!(cli, "deleting application {}/{}", org, app);
+        Ok(cli.applications()
+            .delete_by_organization_key_and_application_key(org, app))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<DeleteApplication as Task>::Result> {
+        decode_status(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <DeleteApplication as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct ListGenerators;
+
+impl Task for ListGenerators {
+    type Result = StdResult<Vec<models::Generator>, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        Ok(cli.generators().get())
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ListGenerators as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ListGenerators as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(generators) => {
+                for generator in generators {
+                    out!(cli, "{}\t{}\t{}\t{}\t{}",
+                         generator.key,
+                         generator.name,
+                         generator.language.as_ref().map(|s| &s[..]).unwrap_or("-"),
+                         generator.visibility,
+                         generator.description.as_ref().map(|s| &s[..]).unwrap_or(""));
+                }
+                Ok(())
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct ShowGenerator<'a> {
+    key: &'a str
+}
+
+impl<'a> Task for ShowGenerator<'a> {
+    type Result = StdResult<models::Generator, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        Ok(cli.generators().get_by_key(self.key))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ShowGenerator as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ShowGenerator as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(generator) => print_generator(cli, &generator),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct CreateGenerator {
+    form: models::GeneratorCreateForm
+}
+
+impl Task for CreateGenerator {
+    type Result = StdResult<models::Generator, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "registering generator {} at {}", self.form.key, self.form.uri);
+        Ok(cli.generators().post(self.form.clone()))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<CreateGenerator as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <CreateGenerator as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(generator) => print_generator(cli, &generator),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct UpdateGenerator<'a> {
+    key: &'a str,
+    form: models::GeneratorUpdateForm
+}
+
+impl<'a> Task for UpdateGenerator<'a> {
+    type Result = StdResult<models::Generator, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "updating generator {}", self.key);
+        Ok(cli.generators().put_by_key(self.key, self.form.clone()))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<UpdateGenerator as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <UpdateGenerator as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(generator) => print_generator(cli, &generator),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct EnableGenerator<'a> {
+    key: &'a str,
+    org: &'a str,
+    enabled: bool
+}
+
+impl<'a> Task for EnableGenerator<'a> {
+    type Result = StdResult<(), Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "{} generator {} for {}",
+             if self.enabled { "enabling" } else { "disabling" },
+             self.key, self.org);
+        let form = models::GeneratorOrgForm { enabled: self.enabled };
+        Ok(cli.generators()
+            .put_orgs_by_key_and_organization_key(self.key, self.org, form))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<EnableGenerator as Task>::Result> {
+        decode_status(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <EnableGenerator as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+fn print_generator(cli: &mut Cli, generator: &models::Generator) -> CliResult<()> {
+    out!(cli, "guid: {}", generator.guid);
+    out!(cli, "key: {}", generator.key);
+    out!(cli, "name: {}", generator.name);
+    out!(cli, "uri: {}", generator.uri);
+    if let Some(ref language) = generator.language {
+        out!(cli, "language: {}", language);
+    }
+    out!(cli, "visibility: {}", generator.visibility);
+    if let Some(ref description) = generator.description {
+        out!(cli, "description: {}", description);
+    }
+    Ok(())
+}
+
+struct ListMemberships<'a> {
+    org: &'a str
+}
+
+impl<'a> Task for ListMemberships<'a> {
+    type Result = StdResult<Vec<models::Membership>, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        Ok(cli.memberships().get(Some(self.org), None))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ListMemberships as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ListMemberships as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(memberships) => {
+                for membership in memberships {
+                    out!(cli, "{}\t{}\t{}",
+                         membership.user.email, membership.user.nickname, membership.role);
+                }
+                Ok(())
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct ListUserMemberships {
+    user: models::User
+}
+
+impl Task for ListUserMemberships {
+    type Result = StdResult<Vec<models::Membership>, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let user_guid = self.user.guid.to_string();
+        Ok(cli.memberships().get(None, Some(&user_guid[..])))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ListUserMemberships as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ListUserMemberships as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(memberships) => {
+                for membership in memberships {
+                    out!(cli, "membership: {} ({})",
+                         membership.organization.key, membership.role);
+                }
+                Ok(())
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct ListMembershipRequests<'a> {
+    org: &'a str
+}
+
+impl<'a> Task for ListMembershipRequests<'a> {
+    type Result = StdResult<Vec<models::MembershipRequest>, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        Ok(cli.membership_requests().get(Some(self.org)))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ListMembershipRequests as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ListMembershipRequests as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(requests) => {
+                for request in requests {
+                    out!(cli, "{}\t{}\t{}",
+                         request.guid, request.user.email, request.role);
+                }
+                Ok(())
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct ReviewMembershipRequest<'a> {
+    guid: &'a str,
+    accept: bool
+}
+
+impl<'a> Task for ReviewMembershipRequest<'a> {
+    type Result = StdResult<(), Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let client = cli.membership_requests();
+        if self.accept {
+            err!(cli, "accepting membership request {}", self.guid);
+            Ok(client.post_accept_by_guid(self.guid))
+        } else {
+            err!(cli, "declining membership request {}", self.guid);
+            Ok(client.post_decline_by_guid(self.guid))
+        }
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ReviewMembershipRequest as Task>::Result> {
+        decode_status(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ReviewMembershipRequest as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct CreateMembershipRequest<'a> {
+    organization: models::Organization,
+    user: models::User,
+    role: &'a str
+}
+
+impl<'a> Task for CreateMembershipRequest<'a> {
+    type Result = StdResult<models::MembershipRequest, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "requesting {} membership of {} for {}",
+             self.role, self.organization.key, self.user.email);
+        Ok(cli.membership_requests().post(
+            &self.organization.guid, &self.user.guid, self.role))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<CreateMembershipRequest as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <CreateMembershipRequest as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(request) => Ok(out!(cli, "{}", request.guid)),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct ListSubscriptions {
+    user: models::User
+}
+
+impl Task for ListSubscriptions {
+    type Result = StdResult<Vec<models::Subscription>, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let user_guid = self.user.guid.to_string();
+        Ok(cli.subscriptions().get(Some(&user_guid[..])))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ListSubscriptions as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ListSubscriptions as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(subscriptions) => {
+                for subscription in subscriptions {
+                    out!(cli, "{}\t{}\t{}",
+                         subscription.guid,
+                         subscription.organization.key,
+                         subscription.publication);
+                }
+                Ok(())
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct Subscribe {
+    form: models::SubscriptionForm
+}
+
+impl Task for Subscribe {
+    type Result = StdResult<models::Subscription, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "subscribing to {} for {}", self.form.publication, self.form.organization_key);
+        Ok(cli.subscriptions().post(self.form.clone()))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<Subscribe as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <Subscribe as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(subscription) => Ok(out!(cli, "{}", subscription.guid)),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct Unsubscribe<'a> {
+    guid: &'a str
+}
+
+impl<'a> Task for Unsubscribe<'a> {
+    type Result = StdResult<(), Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "unsubscribing {}", self.guid);
+        Ok(cli.subscriptions().delete_by_guid(self.guid))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<Unsubscribe as Task>::Result> {
+        decode_status(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <Unsubscribe as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct ListWatches<'a> {
+    user: models::User,
+    repo: Option<Repo<'a>>
+}
+
+impl<'a> Task for ListWatches<'a> {
+    type Result = StdResult<Vec<models::Watch>, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let user_guid = self.user.guid.to_string();
+        let (org, app) = match self.repo {
+            Some(Repo(org, app)) => (Some(org), Some(app)),
+            None => (None, None)
+        };
+        Ok(cli.watches().get(Some(&user_guid[..]), org, app))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ListWatches as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ListWatches as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(watches) => {
+                for watch in watches {
+                    out!(cli, "{}/{}", watch.organization.key, watch.application.key);
+                }
+                Ok(())
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct Watch {
+    form: models::WatchForm
+}
+
+impl Task for Watch {
+    type Result = StdResult<models::Watch, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "watching {}/{}", self.form.organization_key, self.form.application_key);
+        Ok(cli.watches().post(self.form.clone()))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<Watch as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <Watch as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(_) => Ok(()),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct Unwatch<'a> {
+    guid: &'a str,
+    tag: &'a str
+}
+
+impl<'a> Task for Unwatch<'a> {
+    type Result = StdResult<(), Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "unwatching {}", self.tag);
+        Ok(cli.watches().delete_by_guid(self.guid))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<Unwatch as Task>::Result> {
+        decode_status(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <Unwatch as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct ListTokens {
+    user: models::User
+}
+
+impl Task for ListTokens {
+    type Result = StdResult<Vec<models::Token>, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let user_guid = self.user.guid.to_string();
+        Ok(cli.tokens().get_users_by_user_guid(&user_guid[..]))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ListTokens as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ListTokens as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(tokens) => {
+                for token in tokens {
+                    out!(cli, "{}\t{}\t{}\t{}\t{}",
+                         token.guid,
+                         token.masked_token,
+                         token.audit.created_at.to_rfc3339(),
+                         token.audit.updated_at.to_rfc3339(),
+                         token.description.as_ref().map(|s| &s[..]).unwrap_or(""));
+                }
+                Ok(())
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct CreateToken {
+    form: models::TokenForm
+}
+
+impl Task for CreateToken {
+    type Result = StdResult<models::Token, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "creating token for user {}", self.form.user_guid);
+        Ok(cli.tokens().post(self.form.clone()))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<CreateToken as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <CreateToken as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(token) => Ok(out!(cli, "{}\t{}", token.guid, token.masked_token)),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct ShowCleartextToken {
+    guid: String
+}
+
+impl Task for ShowCleartextToken {
+    type Result = StdResult<models::CleartextToken, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        Ok(cli.tokens().get_cleartext_by_guid(&self.guid[..]))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ShowCleartextToken as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ShowCleartextToken as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(cleartext) => Ok(out!(cli, "{}", cleartext.token)),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct RevokeToken<'a> {
+    guid: &'a str
+}
+
+impl<'a> Task for RevokeToken<'a> {
+    type Result = StdResult<(), Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "revoking token {}", self.guid);
+        Ok(cli.tokens().delete_by_guid(self.guid))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<RevokeToken as Task>::Result> {
+        decode_status(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <RevokeToken as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct CreateUser {
+    form: models::UserForm
+}
+
+impl Task for CreateUser {
+    type Result = StdResult<models::User, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "creating user {}", self.form.email);
+        Ok(cli.users().post(self.form.clone()))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<CreateUser as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <CreateUser as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(user) => {
+                out!(cli, "{}", user.guid);
+                Ok(err!(cli, "check {} for an email to confirm the address", user.email))
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct UpdateUser {
+    guid: String,
+    form: models::UserUpdateForm
+}
+
+impl Task for UpdateUser {
+    type Result = StdResult<models::User, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "updating user {}", self.guid);
+        Ok(cli.users().put_by_guid(&self.guid[..], self.form.clone()))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<UpdateUser as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <UpdateUser as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(user) => {
+                out!(cli, "email: {}", user.email);
+                out!(cli, "nickname: {}", user.nickname);
+                if let Some(ref name) = user.name {
+                    out!(cli, "name: {}", name);
+                }
+                Ok(())
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct RequestPasswordReset<'a> {
+    email: &'a str
+}
+
+impl<'a> Task for RequestPasswordReset<'a> {
+    type Result = StdResult<(), Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let form = models::PasswordResetRequest { email: self.email.to_string() };
+        Ok(cli.password_reset_requests().post(form))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<RequestPasswordReset as Task>::Result> {
+        decode_status(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <RequestPasswordReset as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(()) => Ok(err!(cli, "check {} for an email with a password reset token", self.email)),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct ResetPassword<'a> {
+    token: &'a str,
+    password: &'a str
+}
+
+impl<'a> Task for ResetPassword<'a> {
+    type Result = StdResult<models::PasswordResetSuccess, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let form = models::PasswordReset {
+            token: self.token.to_string(),
+            password: self.password.to_string()
+        };
+        Ok(cli.password_resets().post(form))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ResetPassword as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ResetPassword as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(success) => Ok(err!(cli, "changed password of user {}", success.user_guid)),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct ConfirmEmail<'a> {
+    token: &'a str
+}
+
+impl<'a> Task for ConfirmEmail<'a> {
+    type Result = StdResult<(), Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        let form = models::EmailVerificationConfirmationForm { token: self.token.to_string() };
+        Ok(cli.email_verification_confirmations().post(form))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ConfirmEmail as Task>::Result> {
+        decode_status(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ConfirmEmail as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(()) => Ok(err!(cli, "{}", "email address confirmed")),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct Authenticate<'a> {
+    email: &'a str,
+    password: &'a str
+}
+
+impl<'a> Task for Authenticate<'a> {
+    type Result = StdResult<models::User, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "authenticating {}", self.email);
+        Ok(cli.users().post_authenticate(self.email, self.password))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<Authenticate as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <Authenticate as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(user) => Ok(out!(cli, "{}\t{}", user.email, user.nickname)),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct CurrentUser {
+    token: String
+}
+
+impl Task for CurrentUser {
+    type Result = StdResult<Vec<models::User>, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        Ok(cli.users().get(None, Some(&self.token[..])))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<CurrentUser as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <CurrentUser as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(users) => {
+                for user in users {
+                    out!(cli, "{}\t{}", user.email, user.nickname);
+                }
+                Ok(())
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct ListOrganizations;
+
+impl Task for ListOrganizations {
+    type Result = StdResult<Vec<models::Organization>, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        Ok(cli.organizations().get())
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ListOrganizations as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ListOrganizations as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(organizations) => {
+                for org in organizations {
+                    out!(cli, "{}\t{}\t{}", org.key, org.name, org.visibility);
+                }
+                Ok(())
+            },
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct ShowOrganization<'a> {
+    key: &'a str
+}
+
+impl<'a> Task for ShowOrganization<'a> {
+    type Result = StdResult<models::Organization, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        Ok(cli.organizations().get_by_key(self.key))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<ShowOrganization as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <ShowOrganization as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(org) => print_organization(cli, &org),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+fn print_organization(cli: &mut Cli, org: &models::Organization) -> CliResult<()> {
+    out!(cli, "guid: {}", org.guid);
+    out!(cli, "key: {}", org.key);
+    out!(cli, "name: {}", org.name);
+    out!(cli, "namespace: {}", org.namespace);
+    out!(cli, "visibility: {}", org.visibility);
+    for domain in org.domains.iter() {
+        out!(cli, "domain: {}", domain.name);
+    }
+    Ok(())
+}
+
+struct CreateOrganization {
+    form: models::OrganizationForm
+}
+
+impl Task for CreateOrganization {
+    type Result = StdResult<models::Organization, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "creating organization {}", self.form.name);
+        Ok(cli.organizations().post(self.form.clone()))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<CreateOrganization as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <CreateOrganization as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(org) => print_organization(cli, &org),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct AddDomain<'a> {
+    org: &'a str,
+    domain: &'a str
+}
+
+impl<'a> Task for AddDomain<'a> {
+    type Result = StdResult<models::Domain, Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "adding domain {} to {}", self.domain, self.org);
+        let domain = models::Domain { name: self.domain.to_string() };
+        Ok(cli.domains().post_by_organization_key(self.org, domain))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<AddDomain as Task>::Result> {
+        decode_response(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <AddDomain as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(_) => Ok(()),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct RemoveDomain<'a> {
+    org: &'a str,
+    domain: &'a str
+}
+
+impl<'a> Task for RemoveDomain<'a> {
+    type Result = StdResult<(), Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "removing domain {} from {}", self.domain, self.org);
+        Ok(cli.domains()
+            .delete_by_organization_key_and_name(self.org, self.domain))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<RemoveDomain as Task>::Result> {
+        decode_status(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <RemoveDomain as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+struct DeleteOrganization<'a> {
+    key: &'a str
+}
+
+impl<'a> Task for DeleteOrganization<'a> {
+    type Result = StdResult<(), Vec<models::Error>>;
+
+    fn perform_request(&self, cli: &mut Cli) -> CliResult<HttpResult<Response>> {
+        err!(cli, "deleting organization {}", self.key);
+        Ok(cli.organizations().delete_by_key(self.key))
+    }
+
+    fn parse_json(&self, status: StatusCode, json: Json) -> CliResult<<DeleteOrganization as Task>::Result> {
+        decode_status(status, json)
+    }
+
+    fn handle_result(&self, cli: &mut Cli, result: <DeleteOrganization as Task>::Result) -> CliResult<()> {
+        match result {
+            Ok(()) => Ok(()),
+            Err(errors) => report_errors(cli, errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use apidoc::models;
+    use apidoc::models::OriginalType;
+    use rustc_serialize::json;
+    use std::cmp::Ordering;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use super::LockedDependency;
+    use super::Lockfile;
+    use super::Project;
+    use super::SyncMode;
+    use super::compare_versions;
+    use super::content_hash;
+    use super::detect_original_type;
+    use super::write_file;
+
+    const TAG: &'static str = "gilt/apidoc-api:latest";
+    const GUID: &'static str = "0f4c2a2e5d4b4d8e9a632b5b0c1e7d11";
+    const OTHER_GUID: &'static str = "7e1b9c403f2a4c558d0e6a4f1b2c3d44";
+
+    // An empty directory of its own for the test called `name`.
+    fn scratch(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("apidoc-test-{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // Writes a manifest with `contents` to a scratch directory,
+    // returning its path.
+    fn manifest(name: &str, contents: &str) -> PathBuf {
+        let path = scratch(name).join("Apidoc.toml");
+        write_file(&path, contents).unwrap();
+        path
+    }
+
+    fn load(path: &PathBuf) -> Result<Project, String> {
+        Project::load(path.to_str().unwrap()).map_err(|err| err.desc)
+    }
+
+    fn code(guid: &str, source: &str) -> models::Code {
+        json::decode(&format!(
+            "{{ \"generator\": {{ \"guid\": \"{}\", \"key\": \"play_2_3_client\", \
+             \"uri\": \"http://localhost:9000\", \"name\": \"Play 2.3 client\", \
+             \"visibility\": \"public\" }}, \"source\": \"{}\" }}",
+            guid, source)[..]).unwrap()
+    }
+
+    fn locked(guid: &str, hash: &str) -> LockedDependency {
+        LockedDependency {
+            tag: TAG.to_string(),
+            version: "0.9.6".to_string(),
+            generator: "play_2_3_client".to_string(),
+            generator_guid: guid.to_string(),
+            hash: hash.to_string()
+        }
+    }
+
+    #[test]
+    fn compares_version_parts_numerically() {
+        assert_eq!(compare_versions("1.10.0", "1.9.0"), Ordering::Greater);
+        assert_eq!(compare_versions("0.9.6", "0.10.0"), Ordering::Less);
+        assert_eq!(compare_versions("2.0.0", "2.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.0", "1.0.0"), Ordering::Less);
+    }
+
+    #[test]
+    fn orders_pre_releases_before_releases() {
+        assert_eq!(compare_versions("1.0.0-rc1", "1.0.0"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0", "1.0.0-rc1"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0.0-alpha", "1.0.0-beta"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0-rc.2", "1.0.0-rc.10"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0-alpha", "1.0.0-alpha.1"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0-alpha.1", "1.0.0-alpha.beta"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0-rc1", "0.9.0"), Ordering::Greater);
+    }
+
+    #[test]
+    fn detects_annotated_protocol() {
+        let input = "// The example service.\n\
+                     /** Documented. */\n\
+                     @namespace(\"com.example (v1)\")\n\
+                     @java-class(\"Example\") protocol Example {\n\
+                     }\n";
+        assert_eq!(detect_original_type("example.idl", input), Some(OriginalType::AvroIdl));
+    }
+
+    #[test]
+    fn ignores_protocol_outside_declaration() {
+        assert_eq!(detect_original_type("notes.txt", "// protocol Example {\n"), None);
+        assert_eq!(detect_original_type("notes.txt", "the protocol is { unknown"), None);
+        assert_eq!(detect_original_type("notes.txt", "protocol {"), None);
+        assert_eq!(detect_original_type("notes.txt", "{ \"protocol\": "), None);
+    }
+
+    #[test]
+    fn detects_json_types() {
+        assert_eq!(detect_original_type("api.json", "{}"), Some(OriginalType::ApiJson));
+        assert_eq!(
+            detect_original_type("api.json", "{ \"swagger\": \"2.0\" }"),
+            Some(OriginalType::SwaggerJson));
+        assert_eq!(detect_original_type("example.avdl", "{}"), Some(OriginalType::AvroIdl));
+    }
+
+    #[test]
+    fn loads_outputs_relative_to_manifest() {
+        let path = manifest(
+            "relative-outputs",
+            "[dependencies.\"gilt/apidoc-api:0.9.6\"]\n\
+             play_2_3_client = \"app/Client.scala\"\n\
+             ning_1_9_client = \"/src/Client.java\"\n");
+        let project = load(&path).unwrap();
+        assert_eq!(project.dependencies.len(), 1);
+        let dependency = &project.dependencies[0];
+        assert_eq!(dependency.tag, "gilt/apidoc-api:0.9.6");
+        assert_eq!(dependency.outputs, vec![
+            ("ning_1_9_client".to_string(), PathBuf::from("/src/Client.java")),
+            ("play_2_3_client".to_string(), path.parent().unwrap().join("app/Client.scala"))
+        ]);
+    }
+
+    #[test]
+    fn loads_manifest_without_dependencies() {
+        let path = manifest("no-dependencies", "");
+        assert_eq!(load(&path).unwrap().dependencies.len(), 0);
+    }
+
+    #[test]
+    fn rejects_unknown_manifest_keys() {
+        let path = manifest(
+            "unknown-key",
+            "[dependency.\"gilt/apidoc-api:0.9.6\"]\n\
+             play_2_3_client = \"app/Client.scala\"\n");
+        let desc = load(&path).err().unwrap();
+        assert!(desc.starts_with("unknown key `dependency` in manifest"), "{}", desc);
+    }
+
+    #[test]
+    fn rejects_invalid_manifest_entries() {
+        let path = manifest(
+            "output-not-string",
+            "[dependencies.\"gilt/apidoc-api:0.9.6\"]\n\
+             play_2_3_client = 1\n");
+        let desc = load(&path).err().unwrap();
+        assert!(desc.starts_with("output for `play_2_3_client` in `gilt/apidoc-api:0.9.6` \
+                                  is not a string"), "{}", desc);
+        let path = manifest(
+            "tag-without-version",
+            "[dependencies.\"gilt/apidoc-api\"]\n\
+             play_2_3_client = \"app/Client.scala\"\n");
+        assert!(load(&path).is_err());
+    }
+
+    #[test]
+    fn round_trips_lockfile() {
+        let path = scratch("round-trip").join("Apidoc.lock");
+        let hash = content_hash("object Client");
+        Lockfile { dependencies: vec![locked(GUID, &hash[..])] }.save(&path).unwrap();
+        let lock = Lockfile::load(&path).unwrap();
+        assert_eq!(lock.dependencies.len(), 1);
+        assert_eq!(lock.version(TAG), Some("0.9.6"));
+        let dependency = lock.find(TAG, "play_2_3_client").unwrap();
+        assert_eq!(dependency.generator_guid, GUID);
+        assert_eq!(dependency.hash, hash);
+        assert!(lock.find(TAG, "ning_1_9_client").is_none());
+    }
+
+    #[test]
+    fn update_resolves_versions_again() {
+        let dir = scratch("update");
+        let path = dir.join("Apidoc.lock");
+        let missing = dir.join("Missing.lock");
+        Lockfile { dependencies: vec![locked(GUID, "sha256:")] }.save(&path).unwrap();
+        let locked_version = |path: &PathBuf, mode: SyncMode| {
+            Lockfile::previous(path, mode).unwrap().version(TAG).map(|version| version.to_string())
+        };
+        assert_eq!(locked_version(&path, SyncMode::Generate), Some("0.9.6".to_string()));
+        assert_eq!(locked_version(&path, SyncMode::Check), Some("0.9.6".to_string()));
+        assert_eq!(locked_version(&path, SyncMode::Update), None);
+        assert_eq!(locked_version(&missing, SyncMode::Generate), None);
+    }
+
+    #[test]
+    fn verifies_locked_generator_and_hash() {
+        let code = code(GUID, "object Client");
+        let hash = content_hash("object Client");
+        assert!(locked(GUID, &hash[..]).verify(&code, &hash[..]).is_ok());
+        let changed = content_hash("object Changed");
+        assert!(locked(GUID, &hash[..]).verify(&code, &changed[..]).is_err());
+        assert!(locked(OTHER_GUID, &hash[..]).verify(&code, &hash[..]).is_err());
     }
 }
@@ -0,0 +1,261 @@
+use std::cmp;
+
+// Number of unchanged lines shown around each change.
+const CONTEXT: usize = 3;
+
+enum Edit {
+    Keep(usize, usize),
+    Delete(usize),
+    Insert(usize)
+}
+
+// Produces a unified diff of `old` and `new`, in the format of `diff -u`,
+// or `None` if they have the same lines.
+pub fn unified(old: &str, new: &str, old_name: &str, new_name: &str) -> Option<String> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    let edits = edit_script(&a, &b);
+
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (i, edit) in edits.iter().enumerate() {
+        if let &Edit::Keep(..) = edit {
+            continue;
+        }
+        let start = i.saturating_sub(CONTEXT);
+        let end = cmp::min(i + 1 + CONTEXT, edits.len());
+        if let Some(last) = hunks.last_mut() {
+            if start <= last.1 {
+                last.1 = end;
+                continue;
+            }
+        }
+        hunks.push((start, end));
+    }
+    if hunks.is_empty() {
+        return None;
+    }
+
+    // Position in each input before every edit, used for hunk headers.
+    let mut positions = Vec::with_capacity(edits.len());
+    let (mut x, mut y) = (0, 0);
+    for edit in edits.iter() {
+        positions.push((x, y));
+        match edit {
+            &Edit::Keep(..) => { x += 1; y += 1; },
+            &Edit::Delete(_) => x += 1,
+            &Edit::Insert(_) => y += 1
+        }
+    }
+
+    let mut out = format!("--- {}\n+++ {}\n", old_name, new_name);
+    for &(start, end) in hunks.iter() {
+        let mut body = String::new();
+        let (mut old_len, mut new_len) = (0, 0);
+        for edit in edits[start..end].iter() {
+            match edit {
+                &Edit::Keep(i, _) => {
+                    old_len += 1;
+                    new_len += 1;
+                    body.push(' ');
+                    body.push_str(a[i]);
+                },
+                &Edit::Delete(i) => {
+                    old_len += 1;
+                    body.push('-');
+                    body.push_str(a[i]);
+                },
+                &Edit::Insert(j) => {
+                    new_len += 1;
+                    body.push('+');
+                    body.push_str(b[j]);
+                }
+            }
+            body.push('\n');
+        }
+        let (x, y) = positions[start];
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            if old_len > 0 { x + 1 } else { x }, old_len,
+            if new_len > 0 { y + 1 } else { y }, new_len));
+        out.push_str(&body[..]);
+    }
+    Some(out)
+}
+
+// Computes the shortest edit script turning `a` into `b` using the
+// linear space refinement of Myers' O(ND) algorithm, which divides the
+// problem at the middle snake of an optimal path rather than keeping
+// the frontier of every round.
+fn edit_script(a: &[&str], b: &[&str]) -> Vec<Edit> {
+    let offset = a.len() + b.len() + 1;
+    let mut forward = vec![0isize; 2 * offset + 1];
+    let mut backward = vec![0isize; 2 * offset + 1];
+    let mut edits = Vec::with_capacity(a.len() + b.len());
+    diff_range(a, b, 0, 0, &mut forward, &mut backward, &mut edits);
+    edits
+}
+
+// Appends the edits turning `a` into `b` to `edits`, where `a` and `b`
+// start at `x` and `y` in the whole inputs.
+fn diff_range(
+    a: &[&str],
+    b: &[&str],
+    x: usize,
+    y: usize,
+    forward: &mut [isize],
+    backward: &mut [isize],
+    edits: &mut Vec<Edit>
+) {
+    let mut prefix = 0;
+    while prefix < a.len() && prefix < b.len() && a[prefix] == b[prefix] {
+        edits.push(Edit::Keep(x + prefix, y + prefix));
+        prefix += 1;
+    }
+    let mut suffix = 0;
+    while suffix < a.len() - prefix && suffix < b.len() - prefix
+        && a[a.len() - 1 - suffix] == b[b.len() - 1 - suffix] {
+        suffix += 1;
+    }
+    let a_inner = &a[prefix..a.len() - suffix];
+    let b_inner = &b[prefix..b.len() - suffix];
+    let (x_inner, y_inner) = (x + prefix, y + prefix);
+
+    if a_inner.is_empty() {
+        for j in 0..b_inner.len() {
+            edits.push(Edit::Insert(y_inner + j));
+        }
+    } else if b_inner.is_empty() {
+        for i in 0..a_inner.len() {
+            edits.push(Edit::Delete(x_inner + i));
+        }
+    } else {
+        let (start_x, start_y, end_x, end_y) = middle_snake(a_inner, b_inner, forward, backward);
+        diff_range(
+            &a_inner[..start_x], &b_inner[..start_y],
+            x_inner, y_inner,
+            forward, backward, edits);
+        for i in 0..end_x - start_x {
+            edits.push(Edit::Keep(x_inner + start_x + i, y_inner + start_y + i));
+        }
+        diff_range(
+            &a_inner[end_x..], &b_inner[end_y..],
+            x_inner + end_x, y_inner + end_y,
+            forward, backward, edits);
+    }
+
+    for i in 0..suffix {
+        edits.push(Edit::Keep(x + a.len() - suffix + i, y + b.len() - suffix + i));
+    }
+}
+
+// Finds the middle snake of a shortest edit script turning `a` into `b`
+// by searching from both ends at once, returning where it starts and
+// ends. The backward search works on the reversed inputs, so that both
+// searches can share the same logic for following a diagonal.
+fn middle_snake(
+    a: &[&str],
+    b: &[&str],
+    forward: &mut [isize],
+    backward: &mut [isize]
+) -> (usize, usize, usize, usize) {
+    let n = a.len() as isize;
+    let m = b.len() as isize;
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    let offset = (forward.len() / 2) as isize;
+    forward[(offset + 1) as usize] = 0;
+    backward[(offset + 1) as usize] = 0;
+
+    for d in 0..(n + m + 1) / 2 + 1 {
+        let mut k = -d;
+        while k <= d {
+            let i = (k + offset) as usize;
+            let start = if k == -d || (k != d && forward[i - 1] < forward[i + 1]) {
+                forward[i + 1]
+            } else {
+                forward[i - 1] + 1
+            };
+            let mut x = start;
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[i] = x;
+            let reverse_k = delta - k;
+            if odd && reverse_k >= -(d - 1) && reverse_k <= d - 1
+                && x + backward[(reverse_k + offset) as usize] >= n {
+                return (start as usize, (start - k) as usize, x as usize, y as usize);
+            }
+            k += 2;
+        }
+
+        let mut k = -d;
+        while k <= d {
+            let i = (k + offset) as usize;
+            let start = if k == -d || (k != d && backward[i - 1] < backward[i + 1]) {
+                backward[i + 1]
+            } else {
+                backward[i - 1] + 1
+            };
+            let mut x = start;
+            let mut y = x - k;
+            while x < n && y < m && a[(n - 1 - x) as usize] == b[(m - 1 - y) as usize] {
+                x += 1;
+                y += 1;
+            }
+            backward[i] = x;
+            let forward_k = delta - k;
+            if !odd && forward_k >= -d && forward_k <= d
+                && x + forward[(forward_k + offset) as usize] >= n {
+                return ((n - x) as usize, (m - y) as usize, (n - start) as usize, (m - start + k) as usize);
+            }
+            k += 2;
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::unified;
+
+    #[test]
+    fn identical() {
+        assert_eq!(unified("a\nb\n", "a\nb\n", "old", "new"), None);
+        assert_eq!(unified("", "", "old", "new"), None);
+    }
+
+    #[test]
+    fn empty_to_non_empty() {
+        assert_eq!(
+            unified("", "a\nb\n", "old", "new").unwrap(),
+            "--- old\n+++ new\n@@ -0,0 +1,2 @@\n+a\n+b\n");
+        assert_eq!(
+            unified("a\nb\n", "", "old", "new").unwrap(),
+            "--- old\n+++ new\n@@ -1,2 +0,0 @@\n-a\n-b\n");
+    }
+
+    #[test]
+    fn insert_only() {
+        assert_eq!(
+            unified("a\nb\nc\n", "a\nb\nx\nc\n", "old", "new").unwrap(),
+            "--- old\n+++ new\n@@ -1,3 +1,4 @@\n a\n b\n+x\n c\n");
+    }
+
+    #[test]
+    fn delete_only() {
+        assert_eq!(
+            unified("a\nb\nx\nc\n", "a\nb\nc\n", "old", "new").unwrap(),
+            "--- old\n+++ new\n@@ -1,4 +1,3 @@\n a\n b\n-x\n c\n");
+    }
+
+    #[test]
+    fn separate_hunks() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let new = "0\n1\n2\n3\n4\n5\n6\n7\n8\n9\n";
+        assert_eq!(
+            unified(old, new, "old", "new").unwrap(),
+            "--- old\n+++ new\n@@ -1,3 +1,4 @@\n+0\n 1\n 2\n 3\n@@ -7,4 +8,3 @@\n 7\n 8\n 9\n-10\n");
+    }
+}
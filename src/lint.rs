@@ -0,0 +1,365 @@
+extern crate hyper;
+
+use locate;
+use rustc_serialize::json::{Array, Json, Object};
+use self::hyper::Url;
+use std::collections::BTreeSet;
+
+const TOP_LEVEL_KEYS: [&'static str; 12] = [
+    "apidoc", "name", "info", "namespace", "description", "base_url",
+    "imports", "headers", "enums", "unions", "models", "resources"
+];
+
+const PRIMITIVES: [&'static str; 11] = [
+    "boolean", "date-iso8601", "date-time-iso8601", "decimal", "double",
+    "integer", "long", "object", "string", "unit", "uuid"
+];
+
+// Checks the structure of an api.json document without contacting the
+// server, returning a description of every problem found. This covers the
+// common mistakes only; the server remains the authority on validity.
+pub fn check(input: &str) -> Vec<String> {
+    let mut lint = Lint { errors: Vec::new(), types: BTreeSet::new() };
+    match Json::from_str(input) {
+        Ok(Json::Object(ref service)) => {
+            if let Some(root) = locate::parse(input) {
+                lint.duplicates(&root);
+            }
+            lint.service(service)
+        },
+        Ok(_) => lint.error("input must be a JSON object".to_string()),
+        Err(err) => lint.error(format!("input is not valid JSON: {}", err))
+    }
+    lint.errors
+}
+
+struct Lint {
+    errors: Vec<String>,
+    // Names of the enums, models and unions defined by the service.
+    types: BTreeSet<String>
+}
+
+impl Lint {
+    fn error(&mut self, desc: String) {
+        self.errors.push(desc);
+    }
+
+    // Reports definitions with the same name, which a JSON object can't
+    // hold, so decoding it keeps only the last of them.
+    fn duplicates(&mut self, root: &locate::Node) {
+        for key in root.duplicate_keys() {
+            self.error(format!("element[{}] appears more than once", key));
+        }
+        let kinds = [("enums", "Enum"), ("models", "Model"), ("unions", "Union"), ("resources", "Resource")];
+        for &(key, kind) in kinds.iter() {
+            if let Some((_, definitions)) = root.member(key) {
+                for name in definitions.duplicate_keys() {
+                    self.error(format!("{}[{}] appears more than once", kind, name));
+                }
+            }
+        }
+    }
+
+    fn service(&mut self, service: &Object) {
+        for key in service.keys() {
+            if !TOP_LEVEL_KEYS.iter().any(|known| *known == &key[..]) {
+                self.error(format!("Unrecognized element[{}]", key));
+            }
+        }
+
+        match service.get("name") {
+            Some(&Json::String(ref name)) if !name.trim().is_empty() => (),
+            Some(&Json::String(_)) | None => self.error("Missing name".to_string()),
+            Some(_) => self.error("name must be a string".to_string())
+        }
+
+        match service.get("base_url") {
+            Some(&Json::String(ref base_url)) => self.base_url(base_url),
+            Some(_) => self.error("base_url must be a string".to_string()),
+            None => ()
+        }
+
+        let enums = self.object(service, "enums");
+        let models = self.object(service, "models");
+        let unions = self.object(service, "unions");
+        let resources = self.object(service, "resources");
+
+        for (kind, definitions) in vec![("Enum", enums), ("Model", models), ("Union", unions)] {
+            if let Some(definitions) = definitions {
+                for name in definitions.keys() {
+                    if !self.types.insert(name.clone()) {
+                        self.error(format!("{}[{}] has the same name as another enum, model or union", kind, name));
+                    }
+                }
+            }
+        }
+
+        if let Some(enums) = enums {
+            for (name, definition) in enums.iter() {
+                self.enumeration(name, definition);
+            }
+        }
+        if let Some(models) = models {
+            for (name, definition) in models.iter() {
+                self.model(name, definition);
+            }
+        }
+        if let Some(unions) = unions {
+            for (name, definition) in unions.iter() {
+                self.union(name, definition);
+            }
+        }
+        if let Some(resources) = resources {
+            for (name, definition) in resources.iter() {
+                self.resource(name, definition);
+            }
+        }
+    }
+
+    fn object<'a>(&mut self, obj: &'a Object, key: &str) -> Option<&'a Object> {
+        match obj.get(key) {
+            Some(&Json::Object(ref value)) => Some(value),
+            Some(_) => {
+                self.error(format!("{} must be an object", key));
+                None
+            },
+            None => None
+        }
+    }
+
+    fn array<'a>(&mut self, obj: &'a Object, key: &str, context: &str) -> Option<&'a Array> {
+        match obj.get(key) {
+            Some(&Json::Array(ref value)) => Some(value),
+            Some(_) => {
+                self.error(format!("{} {} must be an array", context, key));
+                None
+            },
+            None => None
+        }
+    }
+
+    fn base_url(&mut self, base_url: &str) {
+        match Url::parse(base_url) {
+            Ok(ref url) if url.scheme == "http" || url.scheme == "https" => (),
+            Ok(_) => self.error(format!("base_url[{}] must use http or https", base_url)),
+            Err(err) => self.error(format!("base_url[{}] is not a valid URL: {}", base_url, err))
+        }
+    }
+
+    fn enumeration(&mut self, name: &str, definition: &Json) {
+        let context = format!("Enum[{}]", name);
+        let definition = match definition.as_object() {
+            Some(definition) => definition,
+            None => return self.error(format!("{} must be an object", context))
+        };
+        let values = match self.array(definition, "values", &context[..]) {
+            Some(values) => values,
+            None => return self.error(format!("{} must have values", context))
+        };
+        let mut names = BTreeSet::new();
+        for value in values.iter() {
+            match value.find("name") {
+                Some(&Json::String(ref value)) => {
+                    if !names.insert(value.clone()) {
+                        self.error(format!("{} value[{}] appears more than once", context, value));
+                    }
+                },
+                _ => self.error(format!("{} has a value without a name", context))
+            }
+        }
+    }
+
+    fn model(&mut self, name: &str, definition: &Json) {
+        let context = format!("Model[{}]", name);
+        let definition = match definition.as_object() {
+            Some(definition) => definition,
+            None => return self.error(format!("{} must be an object", context))
+        };
+        let fields = match self.array(definition, "fields", &context[..]) {
+            Some(fields) => fields,
+            None => return self.error(format!("{} must have fields", context))
+        };
+        let mut names = BTreeSet::new();
+        for field in fields.iter() {
+            let field_name = match field.find("name") {
+                Some(&Json::String(ref field_name)) => field_name,
+                _ => {
+                    self.error(format!("{} has a field without a name", context));
+                    continue;
+                }
+            };
+            let field_context = format!("{} field[{}]", context, field_name);
+            if !names.insert(field_name.clone()) {
+                self.error(format!("{} appears more than once", field_context));
+            }
+            match field.find("type") {
+                Some(&Json::String(ref field_type)) => self.reference(&field_context[..], field_type),
+                _ => self.error(format!("{} must have a type", field_context))
+            }
+        }
+    }
+
+    fn union(&mut self, name: &str, definition: &Json) {
+        let context = format!("Union[{}]", name);
+        let types = match definition.find("types") {
+            Some(&Json::Array(ref types)) => types,
+            _ => return self.error(format!("{} must have types", context))
+        };
+        for union_type in types.iter() {
+            match union_type.find("type") {
+                Some(&Json::String(ref union_type)) => self.reference(&context[..], union_type),
+                _ => self.error(format!("{} has an entry without a type", context))
+            }
+        }
+    }
+
+    fn resource(&mut self, name: &str, definition: &Json) {
+        let context = format!("Resource[{}]", name);
+        self.reference(&context[..], name);
+        let definition = match definition.as_object() {
+            Some(definition) => definition,
+            None => return self.error(format!("{} must be an object", context))
+        };
+        let operations = match self.array(definition, "operations", &context[..]) {
+            Some(operations) => operations,
+            None => return self.error(format!("{} must have operations", context))
+        };
+        for operation in operations.iter() {
+            let method = operation.find("method").and_then(|method| method.as_string());
+            let path = operation.find("path").and_then(|path| path.as_string());
+            let operation_context = format!(
+                "{} {}{}",
+                context,
+                method.unwrap_or("<no method>"),
+                path.map(|path| format!(" {}", path)).unwrap_or(String::new()));
+            if method.is_none() {
+                self.error(format!("{} must have a method", operation_context));
+            }
+            if let Some(body) = operation.find("body") {
+                match body.find("type") {
+                    Some(&Json::String(ref body_type)) => {
+                        self.reference(&format!("{} body", operation_context)[..], body_type)
+                    },
+                    _ => self.error(format!("{} body must have a type", operation_context))
+                }
+            }
+            if let Some(parameters) = operation.find("parameters").and_then(|p| p.as_array()) {
+                let mut names = BTreeSet::new();
+                for parameter in parameters.iter() {
+                    let parameter_name = match parameter.find("name") {
+                        Some(&Json::String(ref parameter_name)) => parameter_name,
+                        _ => {
+                            self.error(format!("{} has a parameter without a name", operation_context));
+                            continue;
+                        }
+                    };
+                    let parameter_context = format!("{} parameter[{}]", operation_context, parameter_name);
+                    if !names.insert(parameter_name.clone()) {
+                        self.error(format!("{} appears more than once", parameter_context));
+                    }
+                    match parameter.find("type") {
+                        Some(&Json::String(ref parameter_type)) => {
+                            self.reference(&parameter_context[..], parameter_type)
+                        },
+                        _ => self.error(format!("{} must have a type", parameter_context))
+                    }
+                }
+            }
+            if let Some(responses) = operation.find("responses").and_then(|r| r.as_object()) {
+                for (code, response) in responses.iter() {
+                    let response_context = format!("{} response[{}]", operation_context, code);
+                    match response.find("type") {
+                        Some(&Json::String(ref response_type)) => {
+                            self.reference(&response_context[..], response_type)
+                        },
+                        _ => self.error(format!("{} must have a type", response_context))
+                    }
+                }
+            }
+        }
+    }
+
+    // Reports a type that is neither a primitive nor defined by the service.
+    // Qualified names refer to imported types, which can't be checked here.
+    fn reference(&mut self, context: &str, type_name: &str) {
+        let mut name = type_name;
+        // Collections may be nested, e.g. `[map[string]]`.
+        loop {
+            if name.starts_with("[") && name.ends_with("]") {
+                name = &name[1..name.len() - 1];
+            } else if name.starts_with("map[") && name.ends_with("]") {
+                name = &name[4..name.len() - 1];
+            } else if name == "map" {
+                return;
+            } else {
+                break;
+            }
+        }
+        let primitive = PRIMITIVES.iter().any(|primitive| *primitive == name);
+        if primitive || name.contains(".") || self.types.contains(name) {
+            return;
+        }
+        self.error(format!("{} type[{}] is not defined", context, type_name));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use locate;
+    use locate::Position;
+    use super::check;
+
+    fn check_field_type(field_type: &str) -> Vec<String> {
+        check(&format!(r#"{{
+            "name": "example",
+            "enums": {{ "color": {{ "values": [{{ "name": "red" }}] }} }},
+            "models": {{
+                "user": {{ "fields": [{{ "name": "field", "type": "{}" }}] }}
+            }}
+        }}"#, field_type))
+    }
+
+    #[test]
+    fn accepts_defined_types() {
+        assert!(check_field_type("string").is_empty());
+        assert!(check_field_type("color").is_empty());
+        assert!(check_field_type("user").is_empty());
+        assert!(check_field_type("com.gilt.apidoc.spec.v0.models.service").is_empty());
+    }
+
+    #[test]
+    fn accepts_nested_collections() {
+        assert!(check_field_type("[string]").is_empty());
+        assert!(check_field_type("map").is_empty());
+        assert!(check_field_type("map[color]").is_empty());
+        assert!(check_field_type("[map[string]]").is_empty());
+        assert!(check_field_type("map[[string]]").is_empty());
+        assert!(check_field_type("[[user]]").is_empty());
+    }
+
+    #[test]
+    fn rejects_undefined_types() {
+        assert_eq!(
+            check_field_type("[map[colour]]"),
+            vec!["Model[user] field[field] type[[map[colour]]] is not defined".to_string()]);
+        assert_eq!(
+            check_field_type("colour"),
+            vec!["Model[user] field[field] type[colour] is not defined".to_string()]);
+    }
+
+    #[test]
+    fn rejects_duplicate_definitions() {
+        let input = "{\n  \"name\": \"example\",\n  \"models\": {\n    \
+                     \"user\": { \"fields\": [] },\n    \
+                     \"user\": { \"fields\": [] }\n  }\n}";
+        let errors = check(input);
+        assert_eq!(errors, vec!["Model[user] appears more than once".to_string()]);
+        let root = locate::parse(input).unwrap();
+        assert_eq!(locate::find(&root, &errors[0][..]), Some(Position { line: 5, col: 5 }));
+    }
+
+    #[test]
+    fn accepts_base_url_with_trailing_slash() {
+        assert!(check(r#"{ "name": "example", "base_url": "http://api.example.com/" }"#).is_empty());
+    }
+}
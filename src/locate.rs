@@ -0,0 +1,370 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+macro_rules! try_opt {
+    ($op:expr) => (
+        match $op {
+            Some(val) => val,
+            None => return None
+        }
+    );
+}
+
+// A line and column in an input, both starting at 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    pub line: usize,
+    pub col: usize
+}
+
+// A JSON value along with where it starts in the input. Only as much of
+// the value is kept as is needed to find things in it again.
+pub struct Node {
+    pub position: Position,
+    value: Value
+}
+
+enum Value {
+    // Members along with the position of their key.
+    Object(Vec<(String, Position, Node)>),
+    Array(Vec<Node>),
+    String(String),
+    Other
+}
+
+impl Node {
+    // Finds the member `key` of an object, at the position of its key.
+    // If there are several, the last one is taken, as it is the one a
+    // JSON decoder keeps and where a duplicate is reported.
+    pub fn member(&self, key: &str) -> Option<(Position, &Node)> {
+        match self.value {
+            Value::Object(ref members) => members.iter().rev()
+                .find(|&&(ref name, _, _)| name == key)
+                .map(|&(_, position, ref node)| (position, node)),
+            _ => None
+        }
+    }
+
+    // The keys of an object that appear more than once, each once.
+    pub fn duplicate_keys(&self) -> Vec<&str> {
+        let mut duplicates: Vec<&str> = Vec::new();
+        if let Value::Object(ref members) = self.value {
+            for (i, &(ref name, _, _)) in members.iter().enumerate() {
+                let repeated = members[..i].iter().any(|&(ref other, _, _)| other == name);
+                if repeated && !duplicates.contains(&&name[..]) {
+                    duplicates.push(name);
+                }
+            }
+        }
+        duplicates
+    }
+
+    // Finds the object in an array whose `name` member is `name`, as used
+    // for fields, enum values and parameters. If there are several, the
+    // last one is taken, as that is where a duplicate is reported.
+    pub fn named(&self, name: &str) -> Option<&Node> {
+        match self.value {
+            Value::Array(ref elements) => elements.iter().rev().find(|element| {
+                match element.member("name") {
+                    Some((_, &Node { value: Value::String(ref value), .. })) => value == name,
+                    _ => false
+                }
+            }),
+            _ => None
+        }
+    }
+}
+
+// Parses `input` as JSON, keeping track of where every value starts.
+// Returns `None` if the input is not valid JSON.
+pub fn parse(input: &str) -> Option<Node> {
+    let mut reader = Reader { chars: input.chars().peekable(), line: 1, col: 1 };
+    let node = reader.value();
+    reader.whitespace();
+    match reader.peek() {
+        None => node,
+        Some(_) => None
+    }
+}
+
+// Finds the part of an api.json document that a validation error is
+// about, going by the names it mentions, e.g. `Model[user] field[guid]`.
+pub fn find(root: &Node, message: &str) -> Option<Position> {
+    let mut found = None;
+    let mut current: Option<&Node> = None;
+    for (label, name) in labels(message) {
+        let next = match &label[..] {
+            "Model" => definition(root, "models", name),
+            "Enum" => definition(root, "enums", name),
+            "Union" => definition(root, "unions", name),
+            "Resource" => definition(root, "resources", name),
+            "element" => root.member(name),
+            "base_url" => root.member("base_url"),
+            "field" => current.and_then(|node| element(node, "fields", name)),
+            "value" => current.and_then(|node| element(node, "values", name)),
+            _ => None
+        };
+        if let Some((position, node)) = next {
+            found = Some(position);
+            current = Some(node);
+        }
+    }
+    found
+}
+
+// Formats the line of `input` at `position` with a caret under the column.
+pub fn excerpt(input: &str, position: Position) -> Option<String> {
+    input.lines().nth(position.line - 1).map(|line| {
+        let indent: String = line.chars()
+            .take(position.col - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        format!("{}\n{}^", line, indent)
+    })
+}
+
+fn definition<'a>(root: &'a Node, kind: &str, name: &str) -> Option<(Position, &'a Node)> {
+    root.member(kind).and_then(|(_, definitions)| definitions.member(name))
+}
+
+fn element<'a>(node: &'a Node, key: &str, name: &str) -> Option<(Position, &'a Node)> {
+    node.member(key)
+        .and_then(|(_, elements)| elements.named(name))
+        .map(|element| (element.position, element))
+}
+
+// Splits a message into the `label[name]` pairs it contains.
+fn labels(message: &str) -> Vec<(String, &str)> {
+    let mut labels = Vec::new();
+    let mut rest = message;
+    while let Some(open) = rest.find('[') {
+        let label: String = rest[..open].chars()
+            .rev()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect::<Vec<char>>()
+            .into_iter()
+            .rev()
+            .collect();
+        match rest[open + 1..].find(']') {
+            Some(close) => {
+                labels.push((label, &rest[open + 1..open + 1 + close]));
+                rest = &rest[open + 1 + close + 1..];
+            },
+            None => break
+        }
+    }
+    labels
+}
+
+struct Reader<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    col: usize
+}
+
+impl<'a> Reader<'a> {
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        match c {
+            Some('\n') => {
+                self.line += 1;
+                self.col = 1;
+            },
+            Some(_) => self.col += 1,
+            None => ()
+        }
+        c
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|c| *c)
+    }
+
+    fn position(&self) -> Position {
+        Position { line: self.line, col: self.col }
+    }
+
+    fn whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Option<()> {
+        self.whitespace();
+        match self.next() {
+            Some(c) if c == expected => Some(()),
+            _ => None
+        }
+    }
+
+    fn value(&mut self) -> Option<Node> {
+        self.whitespace();
+        let position = self.position();
+        let value = match self.peek() {
+            Some('{') => try_opt!(self.object()),
+            Some('[') => try_opt!(self.array()),
+            Some('"') => Value::String(try_opt!(self.string())),
+            Some(_) => try_opt!(self.scalar()),
+            None => return None
+        };
+        Some(Node { position: position, value: value })
+    }
+
+    fn object(&mut self) -> Option<Value> {
+        try_opt!(self.expect('{'));
+        let mut members = Vec::new();
+        self.whitespace();
+        if self.peek() == Some('}') {
+            self.next();
+            return Some(Value::Object(members));
+        }
+        loop {
+            self.whitespace();
+            let position = self.position();
+            let key = try_opt!(self.string());
+            try_opt!(self.expect(':'));
+            let node = try_opt!(self.value());
+            members.push((key, position, node));
+            self.whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => return Some(Value::Object(members)),
+                _ => return None
+            }
+        }
+    }
+
+    fn array(&mut self) -> Option<Value> {
+        try_opt!(self.expect('['));
+        let mut elements = Vec::new();
+        self.whitespace();
+        if self.peek() == Some(']') {
+            self.next();
+            return Some(Value::Array(elements));
+        }
+        loop {
+            elements.push(try_opt!(self.value()));
+            self.whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some(']') => return Some(Value::Array(elements)),
+                _ => return None
+            }
+        }
+    }
+
+    fn string(&mut self) -> Option<String> {
+        try_opt!(self.expect('"'));
+        let mut value = String::new();
+        loop {
+            match try_opt!(self.next()) {
+                '"' => return Some(value),
+                '\\' => {
+                    let c = match try_opt!(self.next()) {
+                        'b' => '\x08',
+                        'f' => '\x0c',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => {
+                            let mut code = 0;
+                            for _ in 0..4 {
+                                let digit = try_opt!(self.next().and_then(|c| c.to_digit(16)));
+                                code = code * 16 + digit;
+                            }
+                            // Surrogate pairs don't matter for finding names.
+                            ::std::char::from_u32(code).unwrap_or('\u{fffd}')
+                        },
+                        c => c
+                    };
+                    value.push(c);
+                },
+                c => value.push(c)
+            }
+        }
+    }
+
+    // Numbers, booleans and null, which are only checked loosely.
+    fn scalar(&mut self) -> Option<Value> {
+        let mut token = String::new();
+        while let Some(c) = self.peek() {
+            if !(c.is_alphanumeric() || c == '-' || c == '+' || c == '.') {
+                break;
+            }
+            token.push(c);
+            self.next();
+        }
+        let known = token == "true" || token == "false" || token == "null";
+        let numeric = token.starts_with(|c: char| c == '-' || c.is_digit(10));
+        if known || numeric {
+            Some(Value::Other)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Position;
+    use super::excerpt;
+    use super::find;
+    use super::parse;
+
+    fn at(line: usize, col: usize) -> Option<Position> {
+        Some(Position { line: line, col: col })
+    }
+
+    #[test]
+    fn counts_columns_in_characters() {
+        let input = "{ \"é\": 1, \"名前\": { \"models\": {} } }";
+        let root = parse(input).unwrap();
+        assert_eq!(root.member("é").map(|(position, _)| position), at(1, 3));
+        assert_eq!(root.member("名前").map(|(position, _)| position), at(1, 11));
+        assert_eq!(root.member("名前").map(|(_, node)| node.position), at(1, 17));
+    }
+
+    #[test]
+    fn counts_lines_after_multi_byte_characters() {
+        let input = "{\n  \"description\": \"Ünïcödé ✓\",\n  \"models\": {\n    \"user\": {}\n  }\n}";
+        let root = parse(input).unwrap();
+        assert_eq!(find(&root, "Model[user] must have fields"), at(4, 5));
+    }
+
+    #[test]
+    fn decodes_escaped_strings() {
+        let input = r#"{ "a\"b": 1, "\u00e9": 2, "c:\\": "\t\n", "d": 3 }"#;
+        let root = parse(input).unwrap();
+        assert_eq!(root.member("a\"b").map(|(position, _)| position), at(1, 3));
+        assert_eq!(root.member("é").map(|(position, _)| position), at(1, 14));
+        assert_eq!(root.member("é").map(|(_, node)| node.position), at(1, 24));
+        assert_eq!(root.member("c:\\").map(|(position, _)| position), at(1, 27));
+        assert_eq!(root.member("d").map(|(position, _)| position), at(1, 43));
+        assert!(parse(r#"{ "a": "\u00g9" }"#).is_none());
+        assert!(parse(r#"{ "a": "unterminated }"#).is_none());
+    }
+
+    #[test]
+    fn counts_tabs_as_one_column() {
+        let input = "{\n\t\"models\": {\n\t\t\"user\": {}\n\t}\n}";
+        let root = parse(input).unwrap();
+        assert_eq!(find(&root, "Model[user] must have fields"), at(3, 3));
+        assert_eq!(
+            excerpt(input, Position { line: 3, col: 3 }).unwrap(),
+            "\t\t\"user\": {}\n\t\t^");
+    }
+
+    #[test]
+    fn takes_last_of_duplicate_keys() {
+        let input = "{ \"models\": {}, \"models\": { \"user\": {} }, \"a\": 1, \"a\": 2, \"a\": 3 }";
+        let root = parse(input).unwrap();
+        assert_eq!(root.member("models").map(|(position, _)| position), at(1, 17));
+        assert_eq!(find(&root, "Model[user] must have fields"), at(1, 29));
+        assert_eq!(root.duplicate_keys(), vec!["models", "a"]);
+        assert!(parse("{ \"models\": {} }").unwrap().duplicate_keys().is_empty());
+    }
+}
@@ -2,6 +2,7 @@
 extern crate rustc_serialize;
 extern crate docopt;
 
+use apidoc::models;
 use cli::*;
 use docopt::Docopt;
 use std::env;
@@ -11,19 +12,89 @@ use std::io::Write;
 
 mod apidoc;
 mod cli;
+mod diff;
+mod lint;
+mod locate;
+mod report;
+mod sha256;
 
 #[derive(RustcDecodable)]
 struct Args {
     arg_input: Option<String>,
-    arg_tag: String,
+    arg_app: String,
+    arg_domain: String,
+    arg_email: String,
+    arg_guid: String,
+    arg_key: String,
+    arg_org: String,
+    arg_output: Option<String>,
+    arg_path: Option<String>,
+    arg_publication: models::Publication,
+    arg_tag: Option<String>,
+    arg_token: String,
 
+    cmd_accept: bool,
+    cmd_add: bool,
+    cmd_apps: bool,
     cmd_check: bool,
+    cmd_confirm: bool,
+    cmd_create: bool,
+    cmd_decline: bool,
+    cmd_delete: bool,
+    cmd_disable: bool,
+    cmd_domains: bool,
+    cmd_email: bool,
+    cmd_enable: bool,
     cmd_generate: bool,
+    cmd_generators: bool,
+    cmd_health: bool,
+    cmd_list: bool,
+    cmd_login: bool,
+    cmd_membership_requests: bool,
+    cmd_memberships: bool,
+    cmd_orgs: bool,
+    cmd_password: bool,
+    cmd_pull: bool,
+    cmd_remove: bool,
     cmd_push: bool,
+    cmd_reset: bool,
+    cmd_reset_request: bool,
+    cmd_revoke: bool,
+    cmd_show: bool,
+    cmd_subscribe: bool,
+    cmd_subscriptions: bool,
+    cmd_tokens: bool,
+    cmd_unsubscribe: bool,
+    cmd_update: bool,
+    cmd_users: bool,
+    cmd_unwatch: bool,
+    cmd_versions: bool,
+    cmd_watch: bool,
+    cmd_watches: bool,
+    cmd_whoami: bool,
 
+    flag_api_url: Option<String>,
+    flag_check: bool,
     flag_config: String,
+    flag_description: Option<String>,
+    flag_domain: Vec<String>,
+    flag_email: Option<String>,
+    flag_enabled: Option<String>,
+    flag_format: Option<report::Format>,
+    flag_key: Option<String>,
+    flag_manifest: String,
+    flag_name: Option<String>,
+    flag_namespace: String,
+    flag_nickname: Option<String>,
+    flag_offline: bool,
+    flag_org: String,
+    flag_output: Option<String>,
     flag_profile: String,
-    flag_visibility: apidoc::models::Visibility,
+    flag_role: String,
+    flag_type: Option<models::OriginalType>,
+    flag_uri: String,
+    flag_visibility: Option<models::Visibility>,
+    flag_yes: bool,
 }
 
 impl Args {
@@ -33,6 +104,55 @@ impl Args {
             None => "api.json"
         }
     }
+
+    fn output<'a>(&'a self) -> &'a str {
+        match self.arg_output {
+            Some(ref x) => x,
+            None => "api.json"
+        }
+    }
+
+    fn tag<'a>(&'a self) -> &'a str {
+        match self.arg_tag {
+            Some(ref x) => x,
+            None => ""
+        }
+    }
+
+    fn format(&self) -> report::Format {
+        self.flag_format.unwrap_or(report::Format::Human)
+    }
+
+    fn visibility(&self) -> models::Visibility {
+        self.flag_visibility.clone().unwrap_or(models::Visibility::User)
+    }
+
+    fn application_form(&self) -> models::ApplicationForm {
+        models::ApplicationForm {
+            name: self.flag_name.clone().unwrap_or(String::new()),
+            key: self.flag_key.clone(),
+            description: self.flag_description.clone(),
+            visibility: self.visibility()
+        }
+    }
+
+    fn generator_form(&self) -> models::GeneratorCreateForm {
+        models::GeneratorCreateForm {
+            key: self.flag_key.clone().unwrap_or(String::new()),
+            uri: self.flag_uri.clone(),
+            visibility: self.visibility()
+        }
+    }
+
+    fn organization_form(&self) -> models::OrganizationForm {
+        models::OrganizationForm {
+            name: self.flag_name.clone().unwrap_or(String::new()),
+            key: self.flag_key.clone(),
+            namespace: self.flag_namespace.clone(),
+            visibility: self.flag_visibility.clone(),
+            domains: self.flag_domain.clone()
+        }
+    }
 }
 
 fn main() {
@@ -40,57 +160,371 @@ fn main() {
 
     let usage = format!("
 Usage:
-    apidoc [options] check [<input>]
-    apidoc [options] generate <tag>
-    apidoc [options] push <tag> [<input>]
+    apidoc [options] login [--api-url <url>]
+    apidoc [options] whoami
+    apidoc [options] health
+    apidoc [options] check [--offline] [--type <type>] [--format <format>] [<input>]
+    apidoc [options] generate [--output <file>] [<tag>]
+    apidoc [options] generate --check [<tag> <path>]
+    apidoc [options] pull [--yes] <tag> [<output>]
+    apidoc [options] push [--type <type>] <tag> [<input>]
+    apidoc [options] update
+    apidoc [options] apps list <org>
+    apidoc [options] apps show <app>
+    apidoc [options] apps create <org> --name <name> [--key <new-key>] [--description <description>]
+    apidoc [options] apps delete [--yes] <app>
+    apidoc [options] versions list <app>
+    apidoc [options] versions show <tag>
+    apidoc [options] versions delete [--yes] <tag>
+    apidoc [options] generators list
+    apidoc [options] generators show <key>
+    apidoc [options] generators create --key <new-key> --uri <uri>
+    apidoc [options] generators update <key> [--enabled <enabled>]
+    apidoc [options] generators (enable|disable) <key> --org <org-key>
+    apidoc [options] memberships list <org>
+    apidoc [options] membership-requests list <org>
+    apidoc [options] membership-requests (accept|decline) <guid>
+    apidoc [options] membership-requests create <org> --role <role>
+    apidoc [options] tokens list
+    apidoc [options] tokens create [--description <description>]
+    apidoc [options] tokens revoke <guid>
+    apidoc [options] subscriptions list
+    apidoc [options] subscribe <org> <publication>
+    apidoc [options] unsubscribe <guid>
+    apidoc [options] watches list
+    apidoc [options] watch <app>
+    apidoc [options] unwatch <app>
+    apidoc [options] users create --email <address> [--nickname <nickname>] [--name <name>] [--api-url <url>]
+    apidoc [options] users update [--email <address>] [--nickname <nickname>] [--name <name>]
+    apidoc [options] password reset-request <email> [--api-url <url>]
+    apidoc [options] password reset <token> [--api-url <url>]
+    apidoc [options] email confirm <token> [--api-url <url>]
+    apidoc [options] orgs list
+    apidoc [options] orgs show <key>
+    apidoc [options] orgs create --name <name> --namespace <namespace> [--key <new-key>] [--domain <domain-name>]...
+    apidoc [options] orgs delete [--yes] <key>
+    apidoc [options] domains (add|remove) <org> <domain>
     apidoc --help
 
 Options:
     --config <path-to-config>   [Default: {}/.apidoc/config]
-    --visibility <visibility>   [Default: user]
+    --manifest <manifest>       [Default: Apidoc.toml]
+    --visibility <visibility>   One of user, organization or public.
     --profile <profile>         [Default: default]
+    --api-url <url>             The URL of the apidoc API.
+    --output <file>             Write the generated code to <file>.
+    --type <type>               One of api_json, swagger_json or avro_idl.
+    --format <format>           One of human, json, junit or sarif.
+    --name <name>               The name of the new user, application or organization.
+    --namespace <namespace>     The namespace of the new organization.
+    --key <new-key>             The key of the new application, generator or organization.
+    --domain <domain-name>      A domain of the new organization.
+    --description <description>  A description of the application or token.
+    --uri <uri>                 The URI of the generator service.
+    --enabled <enabled>         Either true or false.
+    --org <org-key>             The key of an organization.
+    --role <role>               The role to request, e.g. member or admin.
+    --email <address>           The email address of the user.
+    --nickname <nickname>       The nickname of the user.
     --help, -h  Print this help.
 
+login - create a token for the CLI by logging in with your email address
+    and password, and save it to the profile in the config.
+    --api-url sets the URL of the apidoc API for the profile. Without
+    it, the URL already in the profile is used, if there is one, as it
+    is by users create, password and email.
+
+whoami - show the profile, API URL, user and organization memberships
+    that commands are run with.
+
+health - check that the apidoc server is reachable and healthy,
+    reporting how long it took to respond.
+
 check - validate <input> against api.apidoc.me/validations.
-    <input> is a path to a JSON description of a service.
+    <input> is a path to a description of a service.
     <input> defaults to api.json.
+    <type> is one of api_json, swagger_json or avro_idl. Without it
+    the type is detected from the extension and content of <input>.
+    api.json input is also checked for common structural mistakes
+    locally. With --offline, only that local check is done. Otherwise
+    the server has the final say, and anything found locally is
+    reported as a warning.
+    <format> is one of human, json, junit or sarif, and defaults to
+    human. The other formats are printed to stdout for use in CI,
+    including errors that keep the input from being checked at all.
 
 generate - generate code based on <tag>.
     <tag> is of the form <organization_key>/<application_key>:<version>/<generator_key>.
         e.g. gilt/apidoc-api:0.9.6/play_2_3_client
         e.g. gilt/apidoc-api:latest/play_2_3_client
+    The code is printed to stdout unless --output is given, in which case
+    it is written to <file>. The file is only replaced once the code has
+    been fetched successfully.
+    Without <tag>, generates code for every dependency listed in the
+    manifest and writes it to the path configured for each generator.
+    Versions are pinned in a lockfile next to the manifest (Apidoc.lock),
+    which is reused on subsequent runs. It fails if the code generated
+    for a locked version no longer matches the generator and SHA-256
+    hash recorded in the lockfile.
+    With --check, nothing is written. Instead the generated code is
+    compared against <path> (or the paths in the manifest), printing
+    a unified diff and failing if they differ.
+
+pull - download the input that was pushed to <tag>.
+    <tag> is of the form <organization_key>/<application_key>:<version>.
+        e.g. gilt/apidoc-api:0.9.6
+    <output> is the path the input is written to.
+    <output> defaults to api.json.
+    Overwriting a different existing <output> asks for confirmation
+    unless --yes is given.
 
 push - push a new revision to <tag> using <input>.
     <tag> is of the form <organization_key>/<application_key>:<version>.
         e.g. gilt/apidoc-api:0.9.6
-    <input> is a path to a JSON description of a service.
+    <input> is a path to a description of a service.
     <input> defaults to api.json.
+    <type> is one of api_json, swagger_json or avro_idl. Without it
+    the type is detected from the extension and content of <input>.
+    <visibility> defaults to user.
+
+update - like generate without <tag>, but resolves every dependency
+    again and rewrites the lockfile.
+
+apps - list, show, create or delete applications.
+    <org> is the key of an organization, e.g. gilt.
+    <app> is of the form <organization_key>/<application_key>.
+        e.g. gilt/apidoc-api
+    <visibility> defaults to user.
+    Deleting an application asks for confirmation unless --yes is given.
+
+versions - list, show or delete versions of an application.
+    show includes the imports, enums, models and operations of the
+    service, as resolved by the server.
+    <app> is of the form <organization_key>/<application_key>.
+    <tag> is of the form <organization_key>/<application_key>:<version>.
+        e.g. gilt/apidoc-api:0.9.6
+        e.g. gilt/apidoc-api:latest
+    Versions are listed in semantic version order, oldest first.
+    Deleting a version asks for confirmation unless --yes is given.
+    Deleting latest resolves it to a concrete version first, which is
+    the one shown in the confirmation and deleted.
+
+generators - list or show the available code generators.
+    <key> is the key of a generator, e.g. play_2_3_client.
+    It is used as the <generator_key> in the tag given to generate.
+    create registers the generator service running at <uri>.
+    update changes the visibility of a generator or, with --enabled
+    true or false, whether it is enabled.
+    enable and disable make a generator available to organization
+    <org-key> or not.
+
+memberships - list the members of organization <org>.
+
+membership-requests - review requests to join organization <org>.
+    <guid> is the guid of a membership request, as shown by list.
+    create requests membership of <org> for the configured user
+    with role <role>, e.g. member or admin.
+
+tokens - list, create or revoke the API tokens of the configured user.
+    create prints the new token. It is only shown this once.
+    <guid> is the guid of a token, as shown by list.
+
+subscriptions - list the subscriptions of the configured user.
+
+subscribe - subscribe to notifications about <publication> in <org>.
+    <publication> is one of
+        membership_requests.create
+        memberships.create
+        applications.create
+        versions.create
+
+unsubscribe - cancel the subscription <guid>, as shown by subscriptions list.
+
+watch - get notified of new versions of application <app>.
+    <app> is of the form <organization_key>/<application_key>.
+    Notifications are only sent with a versions.create subscription.
+
+unwatch - stop watching application <app>.
+
+watches - list the applications watched by the configured user.
+
+users - sign up a new user or update the configured user.
+    create asks for the password of the new user and does not need a config.
+    update changes only the details that are given.
+
+password - request a password reset email for <email>, or reset
+    the password using the <token> from that email.
+    Neither needs a config.
+
+email - confirm an email address using the <token> from the
+    verification email. This does not need a config.
+
+orgs - list, show, create or delete organizations.
+    <key> is the key of an organization, e.g. gilt.
+    Deleting an organization asks for confirmation unless --yes is given.
+
+domains - add or remove a domain of organization <org>.
+    <domain> is a domain name, e.g. gilt.com. Users who confirm an email
+    address in one of the domains of an organization automatically
+    request to join it.
 ", home_dir.display());
 
     let args: Args = Docopt::new(usage)
         .and_then(|d| d.decode())
         .unwrap_or_else(|e| e.exit());
+    let result = if args.cmd_login {
+        login(&args.flag_config, &args.flag_profile, args.flag_api_url.clone())
+    } else if (args.cmd_users && args.cmd_create) || args.cmd_password || args.cmd_email
+        || (args.cmd_check && args.flag_offline) {
+        run_anonymous(&args)
+    } else {
+        run(&args)
+    };
+    match result {
+        Ok(_) => (),
+        Err(err) => {
+            writeln!(&mut stderr(), "{}", err.description()).unwrap();
+            env::set_exit_status(1)
+        }
+    }
+}
+
+// Runs the commands that can be used without a config.
+fn run_anonymous(args: &Args) -> CliResult<()> {
+    // Use the server of the profile, if there is one, even without a token.
+    let api_url = match args.flag_api_url {
+        Some(ref api_url) => Some(api_url.clone()),
+        None => try!(profile_api_url(&args.flag_config, &args.flag_profile))
+    };
+    let mut cli = Cli::anonymous(api_url);
+    if args.cmd_check {
+        cli.check(args.spec(), args.flag_type.as_ref(), true, args.format())
+    } else if args.cmd_users {
+        let email = args.flag_email.clone().unwrap_or(String::new());
+        cli.create_user(&email, args.flag_nickname.clone(), args.flag_name.clone())
+    } else if args.cmd_reset_request {
+        cli.request_password_reset(&args.arg_email)
+    } else if args.cmd_reset {
+        cli.reset_password(&args.arg_token)
+    } else {
+        cli.confirm_email(&args.arg_token)
+    }
+}
+
+fn run(args: &Args) -> CliResult<()> {
     let ref config_path = args.flag_config;
     let ref profile_name = args.flag_profile;
-    let result = Config::load(&config_path, &profile_name).and_then(|config| {
+    Config::load(&config_path, &profile_name).and_then(|config| {
         let mut cli = Cli::new(config);
-        if args.cmd_check {
-            cli.check(args.spec())
+        if args.cmd_whoami {
+            cli.whoami(&args.flag_profile)
+        } else if args.cmd_health {
+            cli.health()
+        } else if args.cmd_check {
+            cli.check(args.spec(), args.flag_type.as_ref(), false, args.format())
+        } else if args.cmd_generate && args.flag_check {
+            match (&args.arg_tag, &args.arg_path) {
+                (&Some(ref tag), &Some(ref path)) => cli.check_generated(tag, path),
+                _ => cli.check_project(&args.flag_manifest)
+            }
         } else if args.cmd_generate {
-            let ref tag = args.arg_tag;
-            cli.generate(tag)
+            match args.arg_tag {
+                Some(ref tag) => {
+                    let output = args.flag_output.as_ref().map(|s| &s[..]);
+                    cli.generate(tag, output)
+                },
+                None => cli.generate_project(&args.flag_manifest)
+            }
+        } else if args.cmd_pull {
+            cli.pull(args.tag(), args.output(), args.flag_yes)
         } else if args.cmd_push {
-            let ref tag = args.arg_tag;
-            cli.push(tag, args.spec(), &args.flag_visibility)
+            cli.push(args.tag(), args.spec(), &args.visibility(), args.flag_type.as_ref())
+        } else if args.cmd_apps {
+            if args.cmd_list {
+                cli.list_applications(&args.arg_org)
+            } else if args.cmd_show {
+                cli.show_application(&args.arg_app)
+            } else if args.cmd_create {
+                cli.create_application(&args.arg_org, args.application_form())
+            } else {
+                cli.delete_application(&args.arg_app, args.flag_yes)
+            }
+        } else if args.cmd_versions {
+            if args.cmd_list {
+                cli.list_versions(&args.arg_app)
+            } else if args.cmd_show {
+                cli.show_version(args.tag())
+            } else {
+                cli.delete_version(args.tag(), args.flag_yes)
+            }
+        } else if args.cmd_generators {
+            if args.cmd_list {
+                cli.list_generators()
+            } else if args.cmd_show {
+                cli.show_generator(&args.arg_key)
+            } else if args.cmd_create {
+                cli.create_generator(args.generator_form())
+            } else if args.cmd_update {
+                let enabled = args.flag_enabled.as_ref().map(|s| &s[..]);
+                cli.update_generator(&args.arg_key, args.flag_visibility.clone(), enabled)
+            } else {
+                cli.enable_generator(&args.arg_key, &args.flag_org, args.cmd_enable)
+            }
+        } else if args.cmd_memberships {
+            cli.list_memberships(&args.arg_org)
+        } else if args.cmd_membership_requests {
+            if args.cmd_list {
+                cli.list_membership_requests(&args.arg_org)
+            } else if args.cmd_create {
+                cli.create_membership_request(&args.arg_org, &args.flag_role)
+            } else {
+                cli.review_membership_request(&args.arg_guid, args.cmd_accept)
+            }
+        } else if args.cmd_tokens {
+            if args.cmd_list {
+                cli.list_tokens()
+            } else if args.cmd_create {
+                cli.create_token(args.flag_description.clone())
+            } else {
+                cli.revoke_token(&args.arg_guid)
+            }
+        } else if args.cmd_subscriptions {
+            cli.list_subscriptions()
+        } else if args.cmd_subscribe {
+            cli.subscribe(&args.arg_org, args.arg_publication.clone())
+        } else if args.cmd_unsubscribe {
+            cli.unsubscribe(&args.arg_guid)
+        } else if args.cmd_watches {
+            cli.list_watches()
+        } else if args.cmd_watch {
+            cli.watch(&args.arg_app)
+        } else if args.cmd_unwatch {
+            cli.unwatch(&args.arg_app)
+        } else if args.cmd_users {
+            cli.update_user(
+                args.flag_email.clone(),
+                args.flag_nickname.clone(),
+                args.flag_name.clone())
+        } else if args.cmd_domains {
+            if args.cmd_add {
+                cli.add_domain(&args.arg_org, &args.arg_domain)
+            } else {
+                cli.remove_domain(&args.arg_org, &args.arg_domain)
+            }
+        } else if args.cmd_orgs {
+            if args.cmd_list {
+                cli.list_organizations()
+            } else if args.cmd_show {
+                cli.show_organization(&args.arg_key)
+            } else if args.cmd_create {
+                cli.create_organization(args.organization_form())
+            } else {
+                cli.delete_organization(&args.arg_key, args.flag_yes)
+            }
+        } else if args.cmd_update {
+            cli.update_project(&args.flag_manifest)
         } else {
             panic!("unkown command")
         }
-    });
-    match result {
-        Ok(_) => (),
-        Err(err) => {
-            writeln!(&mut stderr(), "{}", err.description()).unwrap();
-            env::set_exit_status(1)
-        }
-    }
+    })
 }
@@ -0,0 +1,151 @@
+use locate::Position;
+use rustc_serialize::json::Json;
+use std::collections::BTreeMap;
+use std::fmt;
+
+// How `check` reports the result of a validation.
+#[derive(Clone, Copy, Debug, PartialEq, RustcDecodable)]
+pub enum Format {
+    Human,
+    Json,
+    Junit,
+    Sarif
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Level {
+    Error,
+    Warning
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Level::Error => f.write_str("error"),
+            Level::Warning => f.write_str("warning")
+        }
+    }
+}
+
+// A single validation problem, and where in the input it is if known.
+pub struct Diagnostic {
+    pub level: Level,
+    pub message: String,
+    pub position: Option<Position>
+}
+
+// Formats the diagnostics for `path` as a JSON document, e.g. for tools
+// that don't understand JUnit or SARIF.
+pub fn json(path: &str, diagnostics: &[Diagnostic]) -> String {
+    let results = diagnostics.iter().map(|diagnostic| {
+        let (line, col) = match diagnostic.position {
+            Some(position) => (Json::U64(position.line as u64), Json::U64(position.col as u64)),
+            None => (Json::Null, Json::Null)
+        };
+        object(vec![
+            ("level", Json::String(diagnostic.level.to_string())),
+            ("message", Json::String(diagnostic.message.clone())),
+            ("line", line),
+            ("column", col)
+        ])
+    }).collect();
+    object(vec![
+        ("path", Json::String(path.to_string())),
+        ("valid", Json::Boolean(errors(diagnostics) == 0)),
+        ("diagnostics", Json::Array(results))
+    ]).pretty().to_string()
+}
+
+// Formats the diagnostics for `path` as a JUnit XML test suite with a
+// failing test case per error, or a single passing one if there are
+// none. JUnit has no notion of warnings, so they are left out.
+pub fn junit(path: &str, diagnostics: &[Diagnostic]) -> String {
+    let failures = errors(diagnostics);
+    let mut out = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n".to_string();
+    out.push_str(&format!(
+        "<testsuite name=\"apidoc check\" tests=\"{}\" failures=\"{}\">\n",
+        if failures == 0 { 1 } else { failures },
+        failures));
+    if failures == 0 {
+        out.push_str(&format!(
+            "  <testcase classname=\"apidoc.check\" name=\"{}\"/>\n",
+            escape_xml(path)));
+    }
+    for diagnostic in diagnostics.iter().filter(|diagnostic| diagnostic.level == Level::Error) {
+        let name = match diagnostic.position {
+            Some(position) => format!("{}:{}:{}", path, position.line, position.col),
+            None => path.to_string()
+        };
+        out.push_str(&format!(
+            "  <testcase classname=\"apidoc.check\" name=\"{}\">\n",
+            escape_xml(&name[..])));
+        out.push_str(&format!(
+            "    <failure message=\"{}\"/>\n",
+            escape_xml(&diagnostic.message[..])));
+        out.push_str("  </testcase>\n");
+    }
+    out.push_str("</testsuite>");
+    out
+}
+
+// Formats the diagnostics for `path` as a SARIF 2.1.0 log, as understood
+// by code scanning tools.
+pub fn sarif(path: &str, diagnostics: &[Diagnostic]) -> String {
+    let results = diagnostics.iter().map(|diagnostic| {
+        let mut location = vec![
+            ("artifactLocation", object(vec![("uri", Json::String(path.to_string()))]))
+        ];
+        if let Some(position) = diagnostic.position {
+            location.push(("region", object(vec![
+                ("startLine", Json::U64(position.line as u64)),
+                ("startColumn", Json::U64(position.col as u64))
+            ])));
+        }
+        object(vec![
+            ("ruleId", Json::String("validation".to_string())),
+            ("level", Json::String(diagnostic.level.to_string())),
+            ("message", object(vec![("text", Json::String(diagnostic.message.clone()))])),
+            ("locations", Json::Array(vec![object(vec![("physicalLocation", object(location))])]))
+        ])
+    }).collect();
+    let driver = object(vec![
+        ("name", Json::String("apidoc".to_string())),
+        ("informationUri", Json::String("http://www.apidoc.me".to_string()))
+    ]);
+    let run = object(vec![
+        ("tool", object(vec![("driver", driver)])),
+        ("results", Json::Array(results))
+    ]);
+    object(vec![
+        ("$schema", Json::String("https://json.schemastore.org/sarif-2.1.0.json".to_string())),
+        ("version", Json::String("2.1.0".to_string())),
+        ("runs", Json::Array(vec![run]))
+    ]).pretty().to_string()
+}
+
+fn errors(diagnostics: &[Diagnostic]) -> usize {
+    diagnostics.iter().filter(|diagnostic| diagnostic.level == Level::Error).count()
+}
+
+fn object(members: Vec<(&str, Json)>) -> Json {
+    let mut obj = BTreeMap::new();
+    for (key, value) in members.into_iter() {
+        obj.insert(key.to_string(), value);
+    }
+    Json::Object(obj)
+}
+
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c)
+        }
+    }
+    escaped
+}